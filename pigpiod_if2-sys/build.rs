@@ -1,13 +1,57 @@
 use std::{env, path::PathBuf};
 
 fn main() {
+    println!("cargo:rerun-if-env-changed=PIGPIOD_IF2_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=PIGPIOD_IF2_INCLUDE_DIR");
+
+    let include_paths = link();
+
+    #[cfg(feature = "bindgen")]
+    generate(&include_paths);
+    #[cfg(not(feature = "bindgen"))]
+    let _ = include_paths;
+}
+
+// an explicit directory wins over pkg-config, which wins over the default
+// linker search path, returns where the headers are if known
+fn link() -> Vec<PathBuf> {
+    let include_dir = env::var_os("PIGPIOD_IF2_INCLUDE_DIR").map(PathBuf::from);
+
+    if let Some(lib_dir) = env::var_os("PIGPIOD_IF2_LIB_DIR") {
+        println!(
+            "cargo:rustc-link-search=native={}",
+            PathBuf::from(lib_dir).display()
+        );
+        println!("cargo:rustc-link-lib=pigpiod_if2");
+        return include_dir.into_iter().collect();
+    }
+
+    // pigpio doesn't install a .pc file itself, but distributions may
+    match pkg_config::Config::new().probe("pigpiod_if2") {
+        Ok(library) => include_dir
+            .into_iter()
+            .chain(library.include_paths)
+            .collect(),
+        Err(_) => {
+            println!("cargo:rustc-link-lib=pigpiod_if2");
+            include_dir.into_iter().collect()
+        }
+    }
+}
+
+#[cfg(feature = "bindgen")]
+fn generate(include_paths: &[PathBuf]) {
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
 
-    println!("cargo:return-if-changed=src/wrapper.h");
-    println!("cargo:rustc-link-lib=pigpiod_if2");
+    println!("cargo:rerun-if-changed=src/wrapper.h");
 
     let binds = bindgen::builder()
         .header("src/wrapper.h")
+        .clang_args(
+            include_paths
+                .iter()
+                .map(|path| format!("-I{}", path.display())),
+        )
         .generate()
         .unwrap();
 
@@ -0,0 +1,1304 @@
+/* pregenerated by rust-bindgen 0.69.4 from pigpiod_if2.h of pigpio 79 */
+
+pub const PI_INPUT: u32 = 0;
+pub const PI_OUTPUT: u32 = 1;
+pub const PI_ALT0: u32 = 4;
+pub const PI_ALT1: u32 = 5;
+pub const PI_ALT2: u32 = 6;
+pub const PI_ALT3: u32 = 7;
+pub const PI_ALT4: u32 = 3;
+pub const PI_ALT5: u32 = 2;
+pub const PI_OFF: u32 = 0;
+pub const PI_ON: u32 = 1;
+pub const PI_CLEAR: u32 = 0;
+pub const PI_SET: u32 = 1;
+pub const PI_LOW: u32 = 0;
+pub const PI_HIGH: u32 = 1;
+pub const PI_TIMEOUT: u32 = 2;
+pub const PI_MIN_GPIO: u32 = 0;
+pub const PI_MAX_GPIO: u32 = 53;
+pub const PI_MAX_USER_GPIO: u32 = 31;
+pub const PI_PUD_OFF: u32 = 0;
+pub const PI_PUD_DOWN: u32 = 1;
+pub const PI_PUD_UP: u32 = 2;
+pub const PI_DEFAULT_DUTYCYCLE_RANGE: u32 = 255;
+pub const PI_MIN_DUTYCYCLE_RANGE: u32 = 25;
+pub const PI_MAX_DUTYCYCLE_RANGE: u32 = 40000;
+pub const PI_SERVO_OFF: u32 = 0;
+pub const PI_MIN_SERVO_PULSEWIDTH: u32 = 500;
+pub const PI_MAX_SERVO_PULSEWIDTH: u32 = 2500;
+pub const PI_HW_PWM_MIN_FREQ: u32 = 1;
+pub const PI_HW_PWM_MAX_FREQ: u32 = 125000000;
+pub const PI_HW_PWM_MAX_FREQ_2711: u32 = 187500000;
+pub const PI_HW_PWM_RANGE: u32 = 1000000;
+pub const PI_HW_CLK_MIN_FREQ: u32 = 4689;
+pub const PI_HW_CLK_MIN_FREQ_2711: u32 = 13184;
+pub const PI_HW_CLK_MAX_FREQ: u32 = 250000000;
+pub const PI_HW_CLK_MAX_FREQ_2711: u32 = 375000000;
+pub const PI_NOTIFY_SLOTS: u32 = 32;
+pub const PI_NTFY_FLAGS_EVENT: u32 = 128;
+pub const PI_NTFY_FLAGS_ALIVE: u32 = 64;
+pub const PI_NTFY_FLAGS_WDOG: u32 = 32;
+pub const PI_WAVE_BLOCKS: u32 = 4;
+pub const PI_WAVE_MAX_PULSES: u32 = 12000;
+pub const PI_WAVE_MAX_CHARS: u32 = 2400;
+pub const PI_BB_I2C_MIN_BAUD: u32 = 50;
+pub const PI_BB_I2C_MAX_BAUD: u32 = 500000;
+pub const PI_BB_SPI_MIN_BAUD: u32 = 50;
+pub const PI_BB_SPI_MAX_BAUD: u32 = 250000;
+pub const PI_BB_SER_MIN_BAUD: u32 = 50;
+pub const PI_BB_SER_MAX_BAUD: u32 = 250000;
+pub const PI_BB_SER_NORMAL: u32 = 0;
+pub const PI_BB_SER_INVERT: u32 = 1;
+pub const PI_WAVE_MIN_BAUD: u32 = 50;
+pub const PI_WAVE_MAX_BAUD: u32 = 1000000;
+pub const PI_SPI_MIN_BAUD: u32 = 32000;
+pub const PI_SPI_MAX_BAUD: u32 = 125000000;
+pub const PI_MIN_WAVE_DATABITS: u32 = 1;
+pub const PI_MAX_WAVE_DATABITS: u32 = 32;
+pub const PI_MIN_WAVE_HALFSTOPBITS: u32 = 2;
+pub const PI_MAX_WAVE_HALFSTOPBITS: u32 = 8;
+pub const PI_WAVE_MAX_MICROS: u32 = 1800000000;
+pub const PI_MAX_WAVES: u32 = 250;
+pub const PI_MAX_WAVE_CYCLES: u32 = 65535;
+pub const PI_MAX_WAVE_DELAY: u32 = 65535;
+pub const PI_WAVE_COUNT_PAGES: u32 = 10;
+pub const PI_WAVE_NOT_FOUND: u32 = 9998;
+pub const PI_NO_TX_WAVE: u32 = 9999;
+pub const PI_FILE_SLOTS: u32 = 16;
+pub const PI_I2C_SLOTS: u32 = 512;
+pub const PI_SPI_SLOTS: u32 = 32;
+pub const PI_SER_SLOTS: u32 = 16;
+pub const PI_MAX_I2C_ADDR: u32 = 127;
+pub const PI_NUM_AUX_SPI_CHANNEL: u32 = 3;
+pub const PI_NUM_STD_SPI_CHANNEL: u32 = 2;
+pub const PI_MAX_I2C_DEVICE_COUNT: u32 = 65536;
+pub const PI_MAX_SPI_DEVICE_COUNT: u32 = 65536;
+pub const PI_I2C_RDRW_IOCTL_MAX_MSGS: u32 = 42;
+pub const PI_I2C_M_WR: u32 = 0;
+pub const PI_I2C_M_RD: u32 = 1;
+pub const PI_I2C_M_TEN: u32 = 16;
+pub const PI_I2C_M_RECV_LEN: u32 = 1024;
+pub const PI_I2C_M_NO_RD_ACK: u32 = 2048;
+pub const PI_I2C_M_IGNORE_NAK: u32 = 4096;
+pub const PI_I2C_M_REV_DIR_ADDR: u32 = 8192;
+pub const PI_I2C_M_NOSTART: u32 = 16384;
+pub const PI_I2C_END: u32 = 0;
+pub const PI_I2C_ESC: u32 = 1;
+pub const PI_I2C_START: u32 = 2;
+pub const PI_I2C_COMBINED_ON: u32 = 2;
+pub const PI_I2C_STOP: u32 = 3;
+pub const PI_I2C_COMBINED_OFF: u32 = 3;
+pub const PI_I2C_ADDR: u32 = 4;
+pub const PI_I2C_FLAGS: u32 = 5;
+pub const PI_I2C_READ: u32 = 6;
+pub const PI_I2C_WRITE: u32 = 7;
+pub const PI_SPI_FLAGS_BITLEN_SHIFT: u32 = 16;
+pub const PI_SPI_FLAGS_RX_LSB_SHIFT: u32 = 15;
+pub const PI_SPI_FLAGS_TX_LSB_SHIFT: u32 = 14;
+pub const PI_SPI_FLAGS_3WREN_SHIFT: u32 = 10;
+pub const PI_SPI_FLAGS_3WIRE_SHIFT: u32 = 9;
+pub const PI_SPI_FLAGS_AUX_SHIFT: u32 = 8;
+pub const PI_SPI_FLAGS_RESVD_SHIFT: u32 = 5;
+pub const PI_SPI_FLAGS_CSPOLS_SHIFT: u32 = 2;
+pub const PI_SPI_FLAGS_MODE_SHIFT: u32 = 0;
+pub const PI_WAVE_MODE_ONE_SHOT: u32 = 0;
+pub const PI_WAVE_MODE_REPEAT: u32 = 1;
+pub const PI_WAVE_MODE_ONE_SHOT_SYNC: u32 = 2;
+pub const PI_WAVE_MODE_REPEAT_SYNC: u32 = 3;
+pub const PI_MAX_TIMER: u32 = 9;
+pub const PI_MIN_MS: u32 = 10;
+pub const PI_MAX_MS: u32 = 60000;
+pub const PI_MAX_WDOG_TIMEOUT: u32 = 60000;
+pub const PI_MAX_STEADY: u32 = 300000;
+pub const PI_MAX_ACTIVE: u32 = 1000000;
+pub const PI_MAX_MICS_DELAY: u32 = 1000000;
+pub const PI_MAX_MILS_DELAY: u32 = 60000;
+pub const PI_MAX_SCRIPTS: u32 = 32;
+pub const PI_MAX_SCRIPT_TAGS: u32 = 50;
+pub const PI_MAX_SCRIPT_VARS: u32 = 150;
+pub const PI_MAX_SCRIPT_PARAMS: u32 = 10;
+pub const PI_SCRIPT_INITING: u32 = 0;
+pub const PI_SCRIPT_HALTED: u32 = 1;
+pub const PI_SCRIPT_RUNNING: u32 = 2;
+pub const PI_SCRIPT_WAITING: u32 = 3;
+pub const PI_SCRIPT_FAILED: u32 = 4;
+pub const PI_MAX_PAD: u32 = 2;
+pub const PI_MIN_PAD_STRENGTH: u32 = 1;
+pub const PI_MAX_PAD_STRENGTH: u32 = 16;
+pub const PI_FILE_NONE: u32 = 0;
+pub const PI_FILE_MIN: u32 = 1;
+pub const PI_FILE_READ: u32 = 1;
+pub const PI_FILE_WRITE: u32 = 2;
+pub const PI_FILE_RW: u32 = 3;
+pub const PI_FILE_APPEND: u32 = 4;
+pub const PI_FILE_CREATE: u32 = 8;
+pub const PI_FILE_TRUNC: u32 = 16;
+pub const PI_FILE_MAX: u32 = 31;
+pub const PI_FROM_START: u32 = 0;
+pub const PI_FROM_CURRENT: u32 = 1;
+pub const PI_FROM_END: u32 = 2;
+pub const PI_MAX_EVENT: u32 = 31;
+pub const PI_EVENT_BSC: u32 = 31;
+pub const PI_DEFAULT_SOCKET_PORT: u32 = 8888;
+pub const RISING_EDGE: u32 = 0;
+pub const FALLING_EDGE: u32 = 1;
+pub const EITHER_EDGE: u32 = 2;
+pub const BSC_FIFO_SIZE: u32 = 512;
+pub const PI_CMD_MODES: u32 = 0;
+pub const PI_CMD_MODEG: u32 = 1;
+pub const PI_CMD_PUD: u32 = 2;
+pub const PI_CMD_READ: u32 = 3;
+pub const PI_CMD_WRITE: u32 = 4;
+pub const PI_CMD_PWM: u32 = 5;
+pub const PI_CMD_PRS: u32 = 6;
+pub const PI_CMD_PFS: u32 = 7;
+pub const PI_CMD_SERVO: u32 = 8;
+pub const PI_CMD_WDOG: u32 = 9;
+pub const PI_CMD_BR1: u32 = 10;
+pub const PI_CMD_BR2: u32 = 11;
+pub const PI_CMD_BC1: u32 = 12;
+pub const PI_CMD_BC2: u32 = 13;
+pub const PI_CMD_BS1: u32 = 14;
+pub const PI_CMD_BS2: u32 = 15;
+pub const PI_CMD_TICK: u32 = 16;
+pub const PI_CMD_HWVER: u32 = 17;
+pub const PI_CMD_NO: u32 = 18;
+pub const PI_CMD_NB: u32 = 19;
+pub const PI_CMD_NP: u32 = 20;
+pub const PI_CMD_NC: u32 = 21;
+pub const PI_CMD_PRG: u32 = 22;
+pub const PI_CMD_PFG: u32 = 23;
+pub const PI_CMD_PRRG: u32 = 24;
+pub const PI_CMD_HELP: u32 = 25;
+pub const PI_CMD_PIGPV: u32 = 26;
+pub const PI_CMD_WVCLR: u32 = 27;
+pub const PI_CMD_WVAG: u32 = 28;
+pub const PI_CMD_WVAS: u32 = 29;
+pub const PI_CMD_WVGO: u32 = 30;
+pub const PI_CMD_WVGOR: u32 = 31;
+pub const PI_CMD_WVBSY: u32 = 32;
+pub const PI_CMD_WVHLT: u32 = 33;
+pub const PI_CMD_WVSM: u32 = 34;
+pub const PI_CMD_WVSP: u32 = 35;
+pub const PI_CMD_WVSC: u32 = 36;
+pub const PI_CMD_TRIG: u32 = 37;
+pub const PI_CMD_PROC: u32 = 38;
+pub const PI_CMD_PROCD: u32 = 39;
+pub const PI_CMD_PROCR: u32 = 40;
+pub const PI_CMD_PROCS: u32 = 41;
+pub const PI_CMD_SLRO: u32 = 42;
+pub const PI_CMD_SLR: u32 = 43;
+pub const PI_CMD_SLRC: u32 = 44;
+pub const PI_CMD_PROCP: u32 = 45;
+pub const PI_CMD_MICS: u32 = 46;
+pub const PI_CMD_MILS: u32 = 47;
+pub const PI_CMD_PARSE: u32 = 48;
+pub const PI_CMD_WVCRE: u32 = 49;
+pub const PI_CMD_WVDEL: u32 = 50;
+pub const PI_CMD_WVTX: u32 = 51;
+pub const PI_CMD_WVTXR: u32 = 52;
+pub const PI_CMD_WVNEW: u32 = 53;
+pub const PI_CMD_I2CO: u32 = 54;
+pub const PI_CMD_I2CC: u32 = 55;
+pub const PI_CMD_I2CRD: u32 = 56;
+pub const PI_CMD_I2CWD: u32 = 57;
+pub const PI_CMD_I2CWQ: u32 = 58;
+pub const PI_CMD_I2CRS: u32 = 59;
+pub const PI_CMD_I2CWS: u32 = 60;
+pub const PI_CMD_I2CRB: u32 = 61;
+pub const PI_CMD_I2CWB: u32 = 62;
+pub const PI_CMD_I2CRW: u32 = 63;
+pub const PI_CMD_I2CWW: u32 = 64;
+pub const PI_CMD_I2CRK: u32 = 65;
+pub const PI_CMD_I2CWK: u32 = 66;
+pub const PI_CMD_I2CRI: u32 = 67;
+pub const PI_CMD_I2CWI: u32 = 68;
+pub const PI_CMD_I2CPC: u32 = 69;
+pub const PI_CMD_I2CPK: u32 = 70;
+pub const PI_CMD_SPIO: u32 = 71;
+pub const PI_CMD_SPIC: u32 = 72;
+pub const PI_CMD_SPIR: u32 = 73;
+pub const PI_CMD_SPIW: u32 = 74;
+pub const PI_CMD_SPIX: u32 = 75;
+pub const PI_CMD_SERO: u32 = 76;
+pub const PI_CMD_SERC: u32 = 77;
+pub const PI_CMD_SERRB: u32 = 78;
+pub const PI_CMD_SERWB: u32 = 79;
+pub const PI_CMD_SERR: u32 = 80;
+pub const PI_CMD_SERW: u32 = 81;
+pub const PI_CMD_SERDA: u32 = 82;
+pub const PI_CMD_GDC: u32 = 83;
+pub const PI_CMD_GPW: u32 = 84;
+pub const PI_CMD_HC: u32 = 85;
+pub const PI_CMD_HP: u32 = 86;
+pub const PI_CMD_CF1: u32 = 87;
+pub const PI_CMD_CF2: u32 = 88;
+pub const PI_CMD_BI2CC: u32 = 89;
+pub const PI_CMD_BI2CO: u32 = 90;
+pub const PI_CMD_BI2CZ: u32 = 91;
+pub const PI_CMD_I2CZ: u32 = 92;
+pub const PI_CMD_WVCHA: u32 = 93;
+pub const PI_CMD_SLRI: u32 = 94;
+pub const PI_CMD_CGI: u32 = 95;
+pub const PI_CMD_CSI: u32 = 96;
+pub const PI_CMD_FG: u32 = 97;
+pub const PI_CMD_FN: u32 = 98;
+pub const PI_CMD_NOIB: u32 = 99;
+pub const PI_CMD_WVTXM: u32 = 100;
+pub const PI_CMD_WVTAT: u32 = 101;
+pub const PI_CMD_PADS: u32 = 102;
+pub const PI_CMD_PADG: u32 = 103;
+pub const PI_CMD_FO: u32 = 104;
+pub const PI_CMD_FC: u32 = 105;
+pub const PI_CMD_FR: u32 = 106;
+pub const PI_CMD_FW: u32 = 107;
+pub const PI_CMD_FS: u32 = 108;
+pub const PI_CMD_FL: u32 = 109;
+pub const PI_CMD_SHELL: u32 = 110;
+pub const PI_CMD_BSPIC: u32 = 111;
+pub const PI_CMD_BSPIO: u32 = 112;
+pub const PI_CMD_BSPIX: u32 = 113;
+pub const PI_CMD_BSCX: u32 = 114;
+pub const PI_CMD_EVM: u32 = 115;
+pub const PI_CMD_EVT: u32 = 116;
+pub const PI_CMD_PROCU: u32 = 117;
+pub const PI_CMD_WVCAP: u32 = 118;
+pub const PIGPIOD_IF2_VERSION: u32 = 17;
+pub const PI_DEFAULT_SOCKET_PORT_STR: &[u8; 5] = b"8888\0";
+pub const PI_DEFAULT_SOCKET_ADDR_STR: &[u8; 10] = b"localhost\0";
+pub const PI_INIT_FAILED: i32 = -1;
+pub const PI_BAD_USER_GPIO: i32 = -2;
+pub const PI_BAD_GPIO: i32 = -3;
+pub const PI_BAD_MODE: i32 = -4;
+pub const PI_BAD_LEVEL: i32 = -5;
+pub const PI_BAD_PUD: i32 = -6;
+pub const PI_BAD_PULSEWIDTH: i32 = -7;
+pub const PI_BAD_DUTYCYCLE: i32 = -8;
+pub const PI_BAD_TIMER: i32 = -9;
+pub const PI_BAD_MS: i32 = -10;
+pub const PI_BAD_TIMETYPE: i32 = -11;
+pub const PI_BAD_SECONDS: i32 = -12;
+pub const PI_BAD_MICROS: i32 = -13;
+pub const PI_TIMER_FAILED: i32 = -14;
+pub const PI_BAD_WDOG_TIMEOUT: i32 = -15;
+pub const PI_NO_ALERT_FUNC: i32 = -16;
+pub const PI_BAD_CLK_PERIPH: i32 = -17;
+pub const PI_BAD_CLK_SOURCE: i32 = -18;
+pub const PI_BAD_CLK_MICROS: i32 = -19;
+pub const PI_BAD_BUF_MILLIS: i32 = -20;
+pub const PI_BAD_DUTYRANGE: i32 = -21;
+pub const PI_BAD_DUTY_RANGE: i32 = -21;
+pub const PI_BAD_SIGNUM: i32 = -22;
+pub const PI_BAD_PATHNAME: i32 = -23;
+pub const PI_NO_HANDLE: i32 = -24;
+pub const PI_BAD_HANDLE: i32 = -25;
+pub const PI_BAD_IF_FLAGS: i32 = -26;
+pub const PI_BAD_CHANNEL: i32 = -27;
+pub const PI_BAD_PRIM_CHANNEL: i32 = -27;
+pub const PI_BAD_SOCKET_PORT: i32 = -28;
+pub const PI_BAD_FIFO_COMMAND: i32 = -29;
+pub const PI_BAD_SECO_CHANNEL: i32 = -30;
+pub const PI_NOT_INITIALISED: i32 = -31;
+pub const PI_INITIALISED: i32 = -32;
+pub const PI_BAD_WAVE_MODE: i32 = -33;
+pub const PI_BAD_CFG_INTERNAL: i32 = -34;
+pub const PI_BAD_WAVE_BAUD: i32 = -35;
+pub const PI_TOO_MANY_PULSES: i32 = -36;
+pub const PI_TOO_MANY_CHARS: i32 = -37;
+pub const PI_NOT_SERIAL_GPIO: i32 = -38;
+pub const PI_BAD_SERIAL_STRUC: i32 = -39;
+pub const PI_BAD_SERIAL_BUF: i32 = -40;
+pub const PI_NOT_PERMITTED: i32 = -41;
+pub const PI_SOME_PERMITTED: i32 = -42;
+pub const PI_BAD_WVSC_COMMND: i32 = -43;
+pub const PI_BAD_WVSM_COMMND: i32 = -44;
+pub const PI_BAD_WVSP_COMMND: i32 = -45;
+pub const PI_BAD_PULSELEN: i32 = -46;
+pub const PI_BAD_SCRIPT: i32 = -47;
+pub const PI_BAD_SCRIPT_ID: i32 = -48;
+pub const PI_BAD_SER_OFFSET: i32 = -49;
+pub const PI_GPIO_IN_USE: i32 = -50;
+pub const PI_BAD_SERIAL_COUNT: i32 = -51;
+pub const PI_BAD_PARAM_NUM: i32 = -52;
+pub const PI_DUP_TAG: i32 = -53;
+pub const PI_TOO_MANY_TAGS: i32 = -54;
+pub const PI_BAD_SCRIPT_CMD: i32 = -55;
+pub const PI_BAD_VAR_NUM: i32 = -56;
+pub const PI_NO_SCRIPT_ROOM: i32 = -57;
+pub const PI_NO_MEMORY: i32 = -58;
+pub const PI_SOCK_READ_FAILED: i32 = -59;
+pub const PI_SOCK_WRIT_FAILED: i32 = -60;
+pub const PI_TOO_MANY_PARAM: i32 = -61;
+pub const PI_NOT_HALTED: i32 = -62;
+pub const PI_SCRIPT_NOT_READY: i32 = -62;
+pub const PI_BAD_TAG: i32 = -63;
+pub const PI_BAD_MICS_DELAY: i32 = -64;
+pub const PI_BAD_MILS_DELAY: i32 = -65;
+pub const PI_BAD_WAVE_ID: i32 = -66;
+pub const PI_TOO_MANY_CBS: i32 = -67;
+pub const PI_TOO_MANY_OOL: i32 = -68;
+pub const PI_EMPTY_WAVEFORM: i32 = -69;
+pub const PI_NO_WAVEFORM_ID: i32 = -70;
+pub const PI_I2C_OPEN_FAILED: i32 = -71;
+pub const PI_SER_OPEN_FAILED: i32 = -72;
+pub const PI_SPI_OPEN_FAILED: i32 = -73;
+pub const PI_BAD_I2C_BUS: i32 = -74;
+pub const PI_BAD_I2C_ADDR: i32 = -75;
+pub const PI_BAD_SPI_CHANNEL: i32 = -76;
+pub const PI_BAD_FLAGS: i32 = -77;
+pub const PI_BAD_SPI_SPEED: i32 = -78;
+pub const PI_BAD_SER_DEVICE: i32 = -79;
+pub const PI_BAD_SER_SPEED: i32 = -80;
+pub const PI_BAD_PARAM: i32 = -81;
+pub const PI_I2C_WRITE_FAILED: i32 = -82;
+pub const PI_I2C_READ_FAILED: i32 = -83;
+pub const PI_BAD_SPI_COUNT: i32 = -84;
+pub const PI_SER_WRITE_FAILED: i32 = -85;
+pub const PI_SER_READ_FAILED: i32 = -86;
+pub const PI_SER_READ_NO_DATA: i32 = -87;
+pub const PI_UNKNOWN_COMMAND: i32 = -88;
+pub const PI_SPI_XFER_FAILED: i32 = -89;
+pub const PI_BAD_POINTER: i32 = -90;
+pub const PI_NO_AUX_SPI: i32 = -91;
+pub const PI_NOT_PWM_GPIO: i32 = -92;
+pub const PI_NOT_SERVO_GPIO: i32 = -93;
+pub const PI_NOT_HCLK_GPIO: i32 = -94;
+pub const PI_NOT_HPWM_GPIO: i32 = -95;
+pub const PI_BAD_HPWM_FREQ: i32 = -96;
+pub const PI_BAD_HPWM_DUTY: i32 = -97;
+pub const PI_BAD_HCLK_FREQ: i32 = -98;
+pub const PI_BAD_HCLK_PASS: i32 = -99;
+pub const PI_HPWM_ILLEGAL: i32 = -100;
+pub const PI_BAD_DATABITS: i32 = -101;
+pub const PI_BAD_STOPBITS: i32 = -102;
+pub const PI_MSG_TOOBIG: i32 = -103;
+pub const PI_BAD_MALLOC_MODE: i32 = -104;
+pub const PI_TOO_MANY_SEGS: i32 = -105;
+pub const PI_BAD_I2C_SEG: i32 = -106;
+pub const PI_BAD_SMBUS_CMD: i32 = -107;
+pub const PI_NOT_I2C_GPIO: i32 = -108;
+pub const PI_BAD_I2C_WLEN: i32 = -109;
+pub const PI_BAD_I2C_RLEN: i32 = -110;
+pub const PI_BAD_I2C_CMD: i32 = -111;
+pub const PI_BAD_I2C_BAUD: i32 = -112;
+pub const PI_CHAIN_LOOP_CNT: i32 = -113;
+pub const PI_BAD_CHAIN_LOOP: i32 = -114;
+pub const PI_CHAIN_COUNTER: i32 = -115;
+pub const PI_BAD_CHAIN_CMD: i32 = -116;
+pub const PI_BAD_CHAIN_DELAY: i32 = -117;
+pub const PI_CHAIN_NESTING: i32 = -118;
+pub const PI_CHAIN_TOO_BIG: i32 = -119;
+pub const PI_DEPRECATED: i32 = -120;
+pub const PI_BAD_SER_INVERT: i32 = -121;
+pub const PI_BAD_EDGE: i32 = -122;
+pub const PI_BAD_ISR_INIT: i32 = -123;
+pub const PI_BAD_FOREVER: i32 = -124;
+pub const PI_BAD_FILTER: i32 = -125;
+pub const PI_BAD_PAD: i32 = -126;
+pub const PI_BAD_STRENGTH: i32 = -127;
+pub const PI_FIL_OPEN_FAILED: i32 = -128;
+pub const PI_BAD_FILE_MODE: i32 = -129;
+pub const PI_BAD_FILE_FLAG: i32 = -130;
+pub const PI_BAD_FILE_READ: i32 = -131;
+pub const PI_BAD_FILE_WRITE: i32 = -132;
+pub const PI_FILE_NOT_ROPEN: i32 = -133;
+pub const PI_FILE_NOT_WOPEN: i32 = -134;
+pub const PI_BAD_FILE_SEEK: i32 = -135;
+pub const PI_NO_FILE_MATCH: i32 = -136;
+pub const PI_NO_FILE_ACCESS: i32 = -137;
+pub const PI_FILE_IS_A_DIR: i32 = -138;
+pub const PI_BAD_SHELL_STATUS: i32 = -139;
+pub const PI_BAD_SCRIPT_NAME: i32 = -140;
+pub const PI_BAD_SPI_BAUD: i32 = -141;
+pub const PI_NOT_SPI_GPIO: i32 = -142;
+pub const PI_BAD_EVENT_ID: i32 = -143;
+pub const PI_CMD_INTERRUPTED: i32 = -144;
+pub const PI_NOT_ON_BCM2711: i32 = -145;
+pub const PI_ONLY_ON_BCM2711: i32 = -146;
+pub const PI_PIGIF_ERR_0: i32 = -2000;
+pub const PI_PIGIF_ERR_99: i32 = -2099;
+pub const PI_CUSTOM_ERR_0: i32 = -3000;
+pub const PI_CUSTOM_ERR_999: i32 = -3999;
+pub type pthread_t = ::std::os::raw::c_ulong;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct gpioPulse_t {
+    pub gpioOn: u32,
+    pub gpioOff: u32,
+    pub usDelay: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct bsc_xfer_t {
+    pub control: u32,
+    pub rxCnt: ::std::os::raw::c_int,
+    pub rxBuf: [::std::os::raw::c_char; 512usize],
+    pub txCnt: ::std::os::raw::c_int,
+    pub txBuf: [::std::os::raw::c_char; 512usize],
+}
+pub type gpioThreadFunc_t = ::std::option::Option<
+    unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void) -> *mut ::std::os::raw::c_void,
+>;
+pub type CBFunc_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        level: ::std::os::raw::c_uint,
+        tick: u32,
+    ),
+>;
+pub type CBFuncEx_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        level: ::std::os::raw::c_uint,
+        tick: u32,
+        userdata: *mut ::std::os::raw::c_void,
+    ),
+>;
+pub type evtCBFunc_t = ::std::option::Option<
+    unsafe extern "C" fn(pi: ::std::os::raw::c_int, event: ::std::os::raw::c_uint, tick: u32),
+>;
+pub type evtCBFuncEx_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        pi: ::std::os::raw::c_int,
+        event: ::std::os::raw::c_uint,
+        tick: u32,
+        userdata: *mut ::std::os::raw::c_void,
+    ),
+>;
+pub const pigif_bad_send: _bindgen_ty_1 = -2000;
+pub const pigif_bad_recv: _bindgen_ty_1 = -2001;
+pub const pigif_bad_getaddrinfo: _bindgen_ty_1 = -2002;
+pub const pigif_bad_connect: _bindgen_ty_1 = -2003;
+pub const pigif_bad_socket: _bindgen_ty_1 = -2004;
+pub const pigif_bad_noib: _bindgen_ty_1 = -2005;
+pub const pigif_duplicate_callback: _bindgen_ty_1 = -2006;
+pub const pigif_bad_malloc: _bindgen_ty_1 = -2007;
+pub const pigif_bad_callback: _bindgen_ty_1 = -2008;
+pub const pigif_notify_failed: _bindgen_ty_1 = -2009;
+pub const pigif_callback_not_found: _bindgen_ty_1 = -2010;
+pub const pigif_unconnected_pi: _bindgen_ty_1 = -2011;
+pub const pigif_too_many_pis: _bindgen_ty_1 = -2012;
+pub type _bindgen_ty_1 = ::std::os::raw::c_int;
+extern "C" {
+    pub fn time_time() -> f64;
+}
+extern "C" {
+    pub fn time_sleep(seconds: f64);
+}
+extern "C" {
+    pub fn pigpio_error(errnum: ::std::os::raw::c_int) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn pigpiod_if_version() -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    pub fn start_thread(
+        thread_func: gpioThreadFunc_t,
+        userdata: *mut ::std::os::raw::c_void,
+    ) -> *mut pthread_t;
+}
+extern "C" {
+    pub fn stop_thread(pth: *mut pthread_t);
+}
+extern "C" {
+    pub fn pigpio_start(
+        addrStr: *const ::std::os::raw::c_char,
+        portStr: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn pigpio_stop(pi: ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn set_mode(
+        pi: ::std::os::raw::c_int,
+        gpio: ::std::os::raw::c_uint,
+        mode: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_mode(
+        pi: ::std::os::raw::c_int,
+        gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_pull_up_down(
+        pi: ::std::os::raw::c_int,
+        gpio: ::std::os::raw::c_uint,
+        pud: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn gpio_read(
+        pi: ::std::os::raw::c_int,
+        gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn gpio_write(
+        pi: ::std::os::raw::c_int,
+        gpio: ::std::os::raw::c_uint,
+        level: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_PWM_dutycycle(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        dutycycle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_PWM_dutycycle(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_PWM_range(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        range: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_PWM_range(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_PWM_real_range(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_PWM_frequency(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        frequency: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_PWM_frequency(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_servo_pulsewidth(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        pulsewidth: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_servo_pulsewidth(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn notify_open(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn notify_begin(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        bits: u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn notify_pause(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn notify_close(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_watchdog(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        timeout: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_glitch_filter(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        steady: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_noise_filter(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        steady: ::std::os::raw::c_uint,
+        active: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn read_bank_1(pi: ::std::os::raw::c_int) -> u32;
+}
+extern "C" {
+    pub fn read_bank_2(pi: ::std::os::raw::c_int) -> u32;
+}
+extern "C" {
+    pub fn clear_bank_1(pi: ::std::os::raw::c_int, bits: u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn clear_bank_2(pi: ::std::os::raw::c_int, bits: u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_bank_1(pi: ::std::os::raw::c_int, bits: u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_bank_2(pi: ::std::os::raw::c_int, bits: u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn hardware_clock(
+        pi: ::std::os::raw::c_int,
+        gpio: ::std::os::raw::c_uint,
+        clkfreq: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn hardware_PWM(
+        pi: ::std::os::raw::c_int,
+        gpio: ::std::os::raw::c_uint,
+        PWMfreq: ::std::os::raw::c_uint,
+        PWMduty: u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_current_tick(pi: ::std::os::raw::c_int) -> u32;
+}
+extern "C" {
+    pub fn get_hardware_revision(pi: ::std::os::raw::c_int) -> u32;
+}
+extern "C" {
+    pub fn get_pigpio_version(pi: ::std::os::raw::c_int) -> u32;
+}
+extern "C" {
+    pub fn wave_clear(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_add_new(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_add_generic(
+        pi: ::std::os::raw::c_int,
+        numPulses: ::std::os::raw::c_uint,
+        pulses: *mut gpioPulse_t,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_add_serial(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        baud: ::std::os::raw::c_uint,
+        data_bits: ::std::os::raw::c_uint,
+        stop_bits: ::std::os::raw::c_uint,
+        offset: ::std::os::raw::c_uint,
+        numBytes: ::std::os::raw::c_uint,
+        str_: *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_create(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_create_and_pad(
+        pi: ::std::os::raw::c_int,
+        percent: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_delete(
+        pi: ::std::os::raw::c_int,
+        wave_id: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_send_once(
+        pi: ::std::os::raw::c_int,
+        wave_id: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_send_repeat(
+        pi: ::std::os::raw::c_int,
+        wave_id: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_send_using_mode(
+        pi: ::std::os::raw::c_int,
+        wave_id: ::std::os::raw::c_uint,
+        mode: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_chain(
+        pi: ::std::os::raw::c_int,
+        buf: *mut ::std::os::raw::c_char,
+        bufSize: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_tx_at(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_tx_busy(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_tx_stop(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_micros(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_high_micros(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_max_micros(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_pulses(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_high_pulses(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_max_pulses(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_cbs(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_high_cbs(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wave_get_max_cbs(pi: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn gpio_trigger(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        pulseLen: ::std::os::raw::c_uint,
+        level: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn store_script(
+        pi: ::std::os::raw::c_int,
+        script: *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn run_script(
+        pi: ::std::os::raw::c_int,
+        script_id: ::std::os::raw::c_uint,
+        numPar: ::std::os::raw::c_uint,
+        param: *mut u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn update_script(
+        pi: ::std::os::raw::c_int,
+        script_id: ::std::os::raw::c_uint,
+        numPar: ::std::os::raw::c_uint,
+        param: *mut u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn script_status(
+        pi: ::std::os::raw::c_int,
+        script_id: ::std::os::raw::c_uint,
+        param: *mut u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn stop_script(
+        pi: ::std::os::raw::c_int,
+        script_id: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn delete_script(
+        pi: ::std::os::raw::c_int,
+        script_id: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_serial_read_open(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        baud: ::std::os::raw::c_uint,
+        data_bits: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_serial_read(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_void,
+        bufSize: usize,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_serial_read_close(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_serial_invert(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        invert: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_open(
+        pi: ::std::os::raw::c_int,
+        i2c_bus: ::std::os::raw::c_uint,
+        i2c_addr: ::std::os::raw::c_uint,
+        i2c_flags: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_close(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_write_quick(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        bit: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_write_byte(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        bVal: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_read_byte(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_write_byte_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        bVal: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_write_word_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        wVal: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_read_byte_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_read_word_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_process_call(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        wVal: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_write_block_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_read_block_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_block_process_call(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_read_i2c_block_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_write_i2c_block_data(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        i2c_reg: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_read_device(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_write_device(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn i2c_zip(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        inBuf: *mut ::std::os::raw::c_char,
+        inLen: ::std::os::raw::c_uint,
+        outBuf: *mut ::std::os::raw::c_char,
+        outLen: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_i2c_open(
+        pi: ::std::os::raw::c_int,
+        SDA: ::std::os::raw::c_uint,
+        SCL: ::std::os::raw::c_uint,
+        baud: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_i2c_close(
+        pi: ::std::os::raw::c_int,
+        SDA: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_i2c_zip(
+        pi: ::std::os::raw::c_int,
+        SDA: ::std::os::raw::c_uint,
+        inBuf: *mut ::std::os::raw::c_char,
+        inLen: ::std::os::raw::c_uint,
+        outBuf: *mut ::std::os::raw::c_char,
+        outLen: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_spi_open(
+        pi: ::std::os::raw::c_int,
+        CS: ::std::os::raw::c_uint,
+        MISO: ::std::os::raw::c_uint,
+        MOSI: ::std::os::raw::c_uint,
+        SCLK: ::std::os::raw::c_uint,
+        baud: ::std::os::raw::c_uint,
+        spi_flags: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_spi_close(
+        pi: ::std::os::raw::c_int,
+        CS: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bb_spi_xfer(
+        pi: ::std::os::raw::c_int,
+        CS: ::std::os::raw::c_uint,
+        txBuf: *mut ::std::os::raw::c_char,
+        rxBuf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn spi_open(
+        pi: ::std::os::raw::c_int,
+        spi_channel: ::std::os::raw::c_uint,
+        baud: ::std::os::raw::c_uint,
+        spi_flags: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn spi_close(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn spi_read(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn spi_write(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn spi_xfer(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        txBuf: *mut ::std::os::raw::c_char,
+        rxBuf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn serial_open(
+        pi: ::std::os::raw::c_int,
+        ser_tty: *mut ::std::os::raw::c_char,
+        baud: ::std::os::raw::c_uint,
+        ser_flags: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn serial_close(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn serial_write_byte(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        bVal: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn serial_read_byte(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn serial_write(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn serial_read(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn serial_data_available(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn custom_1(
+        pi: ::std::os::raw::c_int,
+        arg1: ::std::os::raw::c_uint,
+        arg2: ::std::os::raw::c_uint,
+        argx: *mut ::std::os::raw::c_char,
+        argc: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn custom_2(
+        pi: ::std::os::raw::c_int,
+        arg1: ::std::os::raw::c_uint,
+        argx: *mut ::std::os::raw::c_char,
+        argc: ::std::os::raw::c_uint,
+        retBuf: *mut ::std::os::raw::c_char,
+        retMax: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn get_pad_strength(
+        pi: ::std::os::raw::c_int,
+        pad: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn set_pad_strength(
+        pi: ::std::os::raw::c_int,
+        pad: ::std::os::raw::c_uint,
+        padStrength: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn shell_(
+        pi: ::std::os::raw::c_int,
+        scriptName: *mut ::std::os::raw::c_char,
+        scriptString: *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn file_open(
+        pi: ::std::os::raw::c_int,
+        file: *mut ::std::os::raw::c_char,
+        mode: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn file_close(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn file_write(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn file_read(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn file_seek(
+        pi: ::std::os::raw::c_int,
+        handle: ::std::os::raw::c_uint,
+        seekOffset: i32,
+        seekFrom: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn file_list(
+        pi: ::std::os::raw::c_int,
+        fpat: *mut ::std::os::raw::c_char,
+        buf: *mut ::std::os::raw::c_char,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn callback(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        edge: ::std::os::raw::c_uint,
+        f: CBFunc_t,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn callback_ex(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        edge: ::std::os::raw::c_uint,
+        f: CBFuncEx_t,
+        userdata: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn callback_cancel(callback_id: ::std::os::raw::c_uint) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wait_for_edge(
+        pi: ::std::os::raw::c_int,
+        user_gpio: ::std::os::raw::c_uint,
+        edge: ::std::os::raw::c_uint,
+        timeout: f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bsc_xfer(pi: ::std::os::raw::c_int, bscxfer: *mut bsc_xfer_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn bsc_i2c(
+        pi: ::std::os::raw::c_int,
+        i2c_addr: ::std::os::raw::c_int,
+        bscxfer: *mut bsc_xfer_t,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn event_callback(
+        pi: ::std::os::raw::c_int,
+        event: ::std::os::raw::c_uint,
+        f: evtCBFunc_t,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn event_callback_ex(
+        pi: ::std::os::raw::c_int,
+        event: ::std::os::raw::c_uint,
+        f: evtCBFuncEx_t,
+        userdata: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn event_callback_cancel(callback_id: ::std::os::raw::c_uint) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn wait_for_event(
+        pi: ::std::os::raw::c_int,
+        event: ::std::os::raw::c_uint,
+        timeout: f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn event_trigger(
+        pi: ::std::os::raw::c_int,
+        event: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
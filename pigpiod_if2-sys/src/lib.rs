@@ -2,4 +2,9 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+#[cfg(feature = "bindgen")]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+// checked in, so building doesn't need libclang and the pigpio headers
+#[cfg(not(feature = "bindgen"))]
+include!("bindings.rs");
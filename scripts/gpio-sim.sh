@@ -0,0 +1,33 @@
+#!/bin/sh
+# sets up a simulated 54 line chip for the gpiochip backend, needs root
+#
+#   sudo scripts/gpio-sim.sh up     prints the device to pass with --chip
+#   sudo scripts/gpio-sim.sh down
+#
+# line levels can then be read from, and inputs driven through,
+# /sys/devices/platform/<dev_name>/<chip_name>/sim_gpio<N>/{value,pull}
+#
+# the ignored tests in tests/gpio_sim.rs run against it with
+#
+#   CPS_GPIO_SIM_CHIP=<device> cargo test --features gpiochip -- --ignored
+set -e
+
+sim=/sys/kernel/config/gpio-sim/cps
+
+case "$1" in
+up)
+	modprobe gpio-sim
+	mkdir -p "$sim/gpio-bank0"
+	echo 54 > "$sim/gpio-bank0/num_lines"
+	echo 1 > "$sim/live"
+	echo "/dev/$(cat "$sim/gpio-bank0/chip_name")"
+	;;
+down)
+	echo 0 > "$sim/live"
+	rmdir "$sim/gpio-bank0" "$sim"
+	;;
+*)
+	echo "usage: $0 up|down" >&2
+	exit 1
+	;;
+esac
@@ -0,0 +1,146 @@
+use std::io;
+use std::path;
+use std::sync;
+use std::thread;
+use std::time;
+
+use crate::prelude::*;
+
+pub trait GpioBackend {
+    fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()>;
+    fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()>;
+    fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel>;
+    fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle>;
+    fn file_read(&self, handle: &Handle, buf: &mut [u8]) -> Result<usize>;
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()>;
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64>;
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>>;
+    fn file_close(&self, handle: &Handle);
+
+    // backends that can't time pulses themselves fall back to single writes,
+    // which aren't atomic, whatever else drives the pins can get in between
+    fn send_pulses(&self, pulses: &[Pulse]) -> Result<()> {
+        for pulse in pulses {
+            for (gpio, level) in pulse.levels() {
+                self.gpio_write(gpio, level)?;
+            }
+            thread::sleep(pulse.delay());
+        }
+
+        Ok(())
+    }
+
+    // microseconds like pigpio's tick, backends without a clock of their own
+    // count from the first call
+    fn current_tick(&self) -> Result<u32> {
+        static START: sync::OnceLock<time::Instant> = sync::OnceLock::new();
+
+        Ok(START.get_or_init(time::Instant::now).elapsed().as_micros() as u32)
+    }
+}
+
+impl<B: GpioBackend + ?Sized> GpioBackend for &B {
+    fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()> {
+        (**self).set_mode(gpio, mode)
+    }
+
+    fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
+        (**self).gpio_write(gpio, level)
+    }
+
+    fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        (**self).gpio_read(gpio)
+    }
+
+    fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle> {
+        (**self).file_open(path, mode)
+    }
+
+    fn file_read(&self, handle: &Handle, buf: &mut [u8]) -> Result<usize> {
+        (**self).file_read(handle, buf)
+    }
+
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()> {
+        (**self).file_write(handle, buf)
+    }
+
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64> {
+        (**self).file_seek(handle, pos)
+    }
+
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>> {
+        (**self).file_list(pattern)
+    }
+
+    fn file_close(&self, handle: &Handle) {
+        (**self).file_close(handle)
+    }
+
+    fn send_pulses(&self, pulses: &[Pulse]) -> Result<()> {
+        (**self).send_pulses(pulses)
+    }
+
+    fn current_tick(&self) -> Result<u32> {
+        (**self).current_tick()
+    }
+}
+
+impl<B: GpioBackend + ?Sized> GpioBackend for sync::Arc<B> {
+    fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()> {
+        (**self).set_mode(gpio, mode)
+    }
+
+    fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
+        (**self).gpio_write(gpio, level)
+    }
+
+    fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        (**self).gpio_read(gpio)
+    }
+
+    fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle> {
+        (**self).file_open(path, mode)
+    }
+
+    fn file_read(&self, handle: &Handle, buf: &mut [u8]) -> Result<usize> {
+        (**self).file_read(handle, buf)
+    }
+
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()> {
+        (**self).file_write(handle, buf)
+    }
+
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64> {
+        (**self).file_seek(handle, pos)
+    }
+
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>> {
+        (**self).file_list(pattern)
+    }
+
+    fn file_close(&self, handle: &Handle) {
+        (**self).file_close(handle)
+    }
+
+    fn send_pulses(&self, pulses: &[Pulse]) -> Result<()> {
+        (**self).send_pulses(pulses)
+    }
+
+    fn current_tick(&self) -> Result<u32> {
+        (**self).current_tick()
+    }
+}
+
+// shell style wildcards, as pigpiod accepts them for file lists
+#[cfg(any(feature = "testing", all(feature = "gpiochip", target_os = "linux")))]
+pub(crate) fn matches(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            matches(rest, name) || !name.is_empty() && matches(pattern, &name[1..])
+        }
+        (Some((b'?', rest)), Some((_, name))) => matches(rest, name),
+        (Some((p, rest)), Some((c, name))) => p == c && matches(rest, name),
+        _ => false,
+    }
+}
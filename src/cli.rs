@@ -1,4 +1,5 @@
 use std::num;
+use std::path;
 
 use clap::*;
 use cps::prelude::*;
@@ -6,12 +7,21 @@ use cps::prelude::*;
 #[derive(Parser, Debug)]
 pub struct Args {
     #[arg(help = "Address of pigpio daemon")]
+    #[arg(default_value = "localhost")]
     pub address: String,
     #[arg(short, long)]
     #[arg(help = "Port of pigpio daemon")]
     #[arg(default_value = "8888")]
     pub port: String,
     #[arg(short, long)]
+    #[arg(help = "Where the pins are driven from")]
+    #[arg(default_value = "pigpiod")]
+    pub backend: Backend,
+    #[arg(long)]
+    #[arg(help = "GPIO character device used by the gpiochip backend")]
+    #[arg(default_value = "/dev/gpiochip0")]
+    pub chip: path::PathBuf,
+    #[arg(short, long)]
     #[arg(help = "Input pin of shift register")]
     #[arg(default_value = "17")]
     pub input: Gpio,
@@ -31,6 +41,9 @@ pub struct Args {
     #[arg(help = "DS18S20 sensor ID")]
     #[arg(default_value = "10-00080253aa82")]
     pub device: String,
+    #[arg(short = 'w', long)]
+    #[arg(help = "Read the sensor over 1-Wire on this pin instead of w1-gpio")]
+    pub one_wire: Option<Gpio>,
     #[arg(short, long)]
     #[arg(help = "Stop after <COUNT> requests")]
     pub count: Option<num::NonZeroUsize>,
@@ -40,6 +53,23 @@ pub struct Args {
     pub format: Format,
 }
 
+impl Args {
+    // clap can't make an argument conflict with a single value of another
+    pub fn checked(self) -> Self {
+        #[cfg(all(feature = "gpiochip", target_os = "linux"))]
+        if matches!(self.backend, Backend::GpioChip) && self.one_wire.is_some() {
+            Self::command()
+                .error(
+                    error::ErrorKind::ArgumentConflict,
+                    "--one-wire talks to pigpiod and can't be used with --backend gpiochip",
+                )
+                .exit();
+        }
+
+        self
+    }
+}
+
 #[derive(ValueEnum, Debug, Clone, Copy)]
 pub enum Format {
     #[value(name = "txt")]
@@ -47,3 +77,12 @@ pub enum Format {
     #[value(name = "csv")]
     CommaSeperatedValues,
 }
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum Backend {
+    #[value(name = "pigpiod")]
+    Pigpiod,
+    #[cfg(all(feature = "gpiochip", target_os = "linux"))]
+    #[value(name = "gpiochip")]
+    GpioChip,
+}
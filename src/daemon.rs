@@ -0,0 +1,437 @@
+use std::collections;
+use std::io;
+use std::net;
+use std::path;
+use std::result;
+use std::str;
+use std::sync;
+use std::sync::atomic;
+use std::thread;
+use std::time;
+
+use crate::mock::Mock;
+use crate::pi::protocol::*;
+use crate::prelude::*;
+
+// in-process stand-in for pigpiod, serves a backend over the socket interface
+#[derive(Debug)]
+pub struct FakeDaemon<B = Mock> {
+    backend: sync::Arc<B>,
+    addr: net::SocketAddr,
+    stop: sync::Arc<atomic::AtomicBool>,
+    // shut down with the daemon, like pigpiod going away
+    conns: sync::Arc<sync::Mutex<Vec<net::TcpStream>>>,
+}
+
+impl FakeDaemon {
+    pub fn new() -> io::Result<Self> {
+        Self::start(Mock::new())
+    }
+}
+
+impl<B> FakeDaemon<B>
+where
+    B: GpioBackend + Send + Sync + 'static,
+{
+    pub fn start(backend: B) -> io::Result<Self> {
+        Self::bind((net::Ipv4Addr::LOCALHOST, 0).into(), backend)
+    }
+
+    // a fixed address allows a restart to be simulated
+    pub fn bind(addr: net::SocketAddr, backend: B) -> io::Result<Self> {
+        let listener = net::TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let backend = sync::Arc::new(backend);
+        let stop = sync::Arc::new(atomic::AtomicBool::new(false));
+        let conns = sync::Arc::<sync::Mutex<Vec<net::TcpStream>>>::default();
+
+        let server = Server {
+            backend: backend.clone(),
+            started: time::Instant::now(),
+            notify: sync::Arc::default(),
+            waves: sync::Arc::default(),
+            stop: stop.clone(),
+        };
+        let stopped = stop.clone();
+        let accepted = conns.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(atomic::Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                if let Ok(stream) = stream.try_clone() {
+                    lock(&accepted).push(stream);
+                }
+                let server = server.clone();
+                thread::spawn(move || server.serve(stream));
+            }
+        });
+
+        Ok(Self {
+            backend,
+            addr,
+            stop,
+            conns,
+        })
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn addr(&self) -> net::SocketAddr {
+        self.addr
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    pub fn connect(&self) -> Result<Pi<Init>> {
+        let addr = self.addr.ip().to_string();
+        let port = self.addr.port().to_string();
+        Pi::try_with_addr_and_port(&addr, &port)
+    }
+}
+
+impl<B> Drop for FakeDaemon<B> {
+    fn drop(&mut self) {
+        self.stop.store(true, atomic::Ordering::Relaxed);
+        // wake up the accept loop so it sees the flag
+        let _ = net::TcpStream::connect(self.addr);
+
+        for stream in lock(&self.conns).drain(..) {
+            let _ = stream.shutdown(net::Shutdown::Both);
+        }
+    }
+}
+
+fn lock<T>(mutex: &sync::Mutex<T>) -> sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(sync::PoisonError::into_inner)
+}
+
+type Reply = result::Result<(i32, Vec<u8>), i32>;
+
+#[derive(Debug)]
+struct Server<B> {
+    backend: sync::Arc<B>,
+    started: time::Instant,
+    // monitored pins of every notification handle
+    notify: sync::Arc<sync::Mutex<collections::BTreeMap<u32, u32>>>,
+    waves: sync::Arc<sync::Mutex<Waves>>,
+    stop: sync::Arc<atomic::AtomicBool>,
+}
+
+#[derive(Debug, Default)]
+struct Waves {
+    // pulses added since the last wave was created
+    staged: Vec<Pulse>,
+    created: collections::BTreeMap<u32, Vec<Pulse>>,
+}
+
+impl<B> Clone for Server<B> {
+    fn clone(&self) -> Self {
+        Self {
+            backend: self.backend.clone(),
+            started: self.started,
+            notify: self.notify.clone(),
+            waves: self.waves.clone(),
+            stop: self.stop.clone(),
+        }
+    }
+}
+
+impl<B: GpioBackend> Server<B> {
+    fn serve(&self, mut stream: net::TcpStream) -> io::Result<()> {
+        use io::Read;
+
+        loop {
+            let request = Header::read_from(&mut stream)?;
+            let mut ext = vec![0; request.p3 as usize];
+            stream.read_exact(&mut ext)?;
+
+            let (res, ext) = self
+                .dispatch(request, &ext)
+                .unwrap_or_else(|err| (err, Vec::new()));
+
+            let response = Header::new(request.cmd, request.p1, request.p2, res as u32);
+            response.write_to(&mut stream, &ext)?;
+
+            // the rest of the connection only carries reports
+            if request.cmd == PI_CMD_NOIB && res >= 0 {
+                return self.report(res as u32, stream);
+            }
+        }
+    }
+
+    // pigpiod samples the pins, polling them is close enough for tests
+    fn report(&self, handle: u32, mut stream: net::TcpStream) -> io::Result<()> {
+        use io::Write;
+
+        let mut seqno = 0u16;
+        let mut last = self.bank(0);
+
+        while let Some(bits) = self.notify().get(&handle).copied() {
+            if self.stop.load(atomic::Ordering::Relaxed) {
+                break;
+            }
+
+            let level = self.bank(0);
+
+            if (level ^ last) & bits != 0 {
+                let report = Report {
+                    seqno,
+                    flags: 0,
+                    tick: self.tick(),
+                    level,
+                };
+                stream.write_all(&report.to_bytes())?;
+                seqno = seqno.wrapping_add(1);
+            }
+
+            last = level;
+            thread::sleep(time::Duration::from_millis(1));
+        }
+
+        Ok(())
+    }
+
+    // the levels of the 32 gpios from `first` on, as far as there are any
+    fn bank(&self, first: u32) -> u32 {
+        (first..first + 32)
+            .filter_map(Gpio::new)
+            .filter(|&gpio| self.backend.gpio_read(gpio).ok() == Some(GpioLevel::High))
+            .fold(0, |bits, gpio| bits | 1 << (gpio.get() - first))
+    }
+
+    fn tick(&self) -> u32 {
+        self.started.elapsed().as_micros() as u32
+    }
+
+    fn notify(&self) -> sync::MutexGuard<'_, collections::BTreeMap<u32, u32>> {
+        lock(&self.notify)
+    }
+
+    fn waves(&self) -> sync::MutexGuard<'_, Waves> {
+        lock(&self.waves)
+    }
+
+    fn dispatch(&self, request: Header, ext: &[u8]) -> Reply {
+        let Header { cmd, p1, p2, .. } = request;
+        let backend = self.backend.as_ref();
+
+        let res = match cmd {
+            PI_CMD_MODES => {
+                backend
+                    .set_mode(gpio(p1)?, mode(p2)?)
+                    .map_err(|_| PI_BAD_GPIO)?;
+                0
+            }
+            PI_CMD_PUD => {
+                gpio(p1)?;
+                if p2 > PI_PUD_UP {
+                    return Err(PI_BAD_PUD);
+                }
+                0
+            }
+            PI_CMD_READ => backend.gpio_read(gpio(p1)?).map_err(|_| PI_BAD_GPIO)? as i32,
+            PI_CMD_WRITE => {
+                backend
+                    .gpio_write(gpio(p1)?, level(p2)?)
+                    .map_err(|_| PI_BAD_GPIO)?;
+                0
+            }
+            PI_CMD_FO => {
+                let path = str::from_utf8(ext).map_err(|_| PI_NO_FILE_MATCH)?;
+                let handle = backend
+                    .file_open(path::Path::new(path), file_mode(p1)?)
+                    .map_err(|_| PI_FIL_OPEN_FAILED)?;
+                handle.0 as i32
+            }
+            PI_CMD_FR => {
+                // same cap as pigpiod
+                let mut buf = vec![0; p2.min(PI_MAX_FILE_READ) as usize];
+                let count = backend
+                    .file_read(&Handle(p1), &mut buf)
+                    .map_err(|_| PI_BAD_HANDLE)?;
+                buf.truncate(count);
+                return Ok((count as i32, buf));
+            }
+            PI_CMD_FW => {
+                backend
+                    .file_write(&Handle(p1), ext)
+                    .map_err(|err| code(err, PI_BAD_HANDLE))?;
+                0
+            }
+            PI_CMD_FS => {
+                let from = ext
+                    .try_into()
+                    .map(u32::from_le_bytes)
+                    .map_err(|_| PI_BAD_FILE_SEEK)?;
+                let offset = i64::from(p2 as i32);
+                let pos = match from {
+                    PI_FROM_START => io::SeekFrom::Start(offset.max(0) as u64),
+                    PI_FROM_CURRENT => io::SeekFrom::Current(offset),
+                    PI_FROM_END => io::SeekFrom::End(offset),
+                    _ => return Err(PI_BAD_FILE_SEEK),
+                };
+                backend
+                    .file_seek(&Handle(p1), pos)
+                    .map_err(|err| code(err, PI_BAD_FILE_SEEK))? as i32
+            }
+            PI_CMD_FL => {
+                let pattern = str::from_utf8(ext).map_err(|_| PI_NO_FILE_MATCH)?;
+                let paths = backend
+                    .file_list(pattern)
+                    .map_err(|err| code(err, PI_NO_FILE_MATCH))?;
+                let mut buf = paths
+                    .iter()
+                    .flat_map(|path| format!("{}\n", path.display()).into_bytes())
+                    .collect::<Vec<_>>();
+                buf.truncate(p1 as usize);
+                return Ok((buf.len() as i32, buf));
+            }
+            PI_CMD_FC => {
+                backend.file_close(&Handle(p1));
+                0
+            }
+            PI_CMD_BR1 => self.bank(0) as i32,
+            PI_CMD_BR2 => self.bank(BANK_2) as i32,
+            PI_CMD_BS1 | PI_CMD_BC1 | PI_CMD_BS2 | PI_CMD_BC2 => {
+                let level = GpioLevel::from(cmd == PI_CMD_BS1 || cmd == PI_CMD_BS2);
+                let first = if cmd == PI_CMD_BS1 || cmd == PI_CMD_BC1 {
+                    0
+                } else {
+                    BANK_2
+                };
+                for bit in (0..32).filter(|bit| p1 >> bit & 1 == 1) {
+                    backend
+                        .gpio_write(gpio(first + bit)?, level)
+                        .map_err(|_| PI_BAD_GPIO)?;
+                }
+                0
+            }
+            PI_CMD_TICK => self.tick() as i32,
+            PI_CMD_NOIB => {
+                let mut notify = self.notify();
+                let handle = (0..)
+                    .find(|handle| !notify.contains_key(handle))
+                    .unwrap_or(0);
+                notify.insert(handle, 0);
+                handle as i32
+            }
+            PI_CMD_NB | PI_CMD_NP => {
+                let bits = if cmd == PI_CMD_NB { p2 } else { 0 };
+                let mut notify = self.notify();
+                *notify.get_mut(&p1).ok_or(PI_BAD_HANDLE)? = bits;
+                0
+            }
+            PI_CMD_NC => {
+                self.notify().remove(&p1).ok_or(PI_BAD_HANDLE)?;
+                0
+            }
+            PI_CMD_WVCLR => {
+                *self.waves() = Waves::default();
+                0
+            }
+            PI_CMD_WVNEW => {
+                self.waves().staged.clear();
+                0
+            }
+            PI_CMD_WVAG => {
+                let mut waves = self.waves();
+                waves.staged.extend(ext.chunks_exact(12).map(|pulse| {
+                    let word = |i: usize| u32::from_le_bytes(pulse[i..i + 4].try_into().unwrap());
+                    Pulse::from_bits(word(0), word(4), word(8))
+                }));
+                waves.staged.len() as i32
+            }
+            PI_CMD_WVCRE => {
+                let mut waves = self.waves();
+                if waves.staged.is_empty() {
+                    return Err(PI_EMPTY_WAVEFORM);
+                }
+                let id = (0..)
+                    .find(|id| !waves.created.contains_key(id))
+                    .unwrap_or(0);
+                let pulses = std::mem::take(&mut waves.staged);
+                waves.created.insert(id, pulses);
+                id as i32
+            }
+            PI_CMD_WVDEL => {
+                self.waves().created.remove(&p1).ok_or(PI_BAD_WAVE_ID)?;
+                0
+            }
+            PI_CMD_WVTX => {
+                let pulses = self
+                    .waves()
+                    .created
+                    .get(&p1)
+                    .cloned()
+                    .ok_or(PI_BAD_WAVE_ID)?;
+                // sent right away, so the wave is never busy afterwards
+                backend.send_pulses(&pulses).map_err(|_| PI_BAD_GPIO)?;
+                pulses.len() as i32
+            }
+            PI_CMD_WVBSY => 0,
+            _ => return Err(PI_UNKNOWN_COMMAND),
+        };
+
+        Ok((res, Vec::new()))
+    }
+}
+
+const PI_MAX_FILE_READ: u32 = 1 << 16;
+
+// the first gpio of bank 2
+const BANK_2: u32 = PI_MAX_USER_GPIO + 1;
+
+fn gpio(raw: u32) -> result::Result<Gpio, i32> {
+    Gpio::new(raw).ok_or(PI_BAD_GPIO)
+}
+
+fn mode(raw: u32) -> result::Result<GpioMode, i32> {
+    match raw {
+        PI_INPUT => Ok(GpioMode::Input),
+        PI_OUTPUT => Ok(GpioMode::Output),
+        PI_ALT0 => Ok(GpioMode::Alt0),
+        PI_ALT1 => Ok(GpioMode::Alt1),
+        PI_ALT2 => Ok(GpioMode::Alt2),
+        PI_ALT3 => Ok(GpioMode::Alt3),
+        PI_ALT4 => Ok(GpioMode::Alt4),
+        PI_ALT5 => Ok(GpioMode::Alt5),
+        _ => Err(PI_BAD_MODE),
+    }
+}
+
+fn level(raw: u32) -> result::Result<GpioLevel, i32> {
+    match raw {
+        PI_LOW => Ok(GpioLevel::Low),
+        PI_HIGH => Ok(GpioLevel::High),
+        _ => Err(PI_BAD_LEVEL),
+    }
+}
+
+fn file_mode(raw: u32) -> result::Result<FileMode, i32> {
+    const CREATE: u32 = PI_FILE_WRITE | PI_FILE_CREATE | PI_FILE_TRUNC;
+    const APPEND: u32 = PI_FILE_WRITE | PI_FILE_CREATE | PI_FILE_APPEND;
+
+    match raw {
+        CREATE => Ok(FileMode::Create),
+        APPEND => Ok(FileMode::Append),
+        _ => match raw & PI_FILE_RW {
+            PI_FILE_READ => Ok(FileMode::Read),
+            PI_FILE_WRITE => Ok(FileMode::Write),
+            PI_FILE_RW => Ok(FileMode::RW),
+            _ => Err(PI_BAD_FILE_MODE),
+        },
+    }
+}
+
+// keeps the daemon's code when the backend failed with one
+fn code(err: Error, default: i32) -> i32 {
+    err.code().map_or(default, |code| code.code())
+}
@@ -0,0 +1,334 @@
+// drives the pins through the kernel's gpio character device (uAPI v2),
+// for running on the Pi itself without pigpiod, files are local ones
+
+use std::collections;
+use std::ffi;
+use std::fs;
+use std::io;
+use std::mem;
+use std::os::fd;
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::path;
+use std::sync;
+
+use crate::backend;
+use crate::prelude::*;
+
+// from linux/gpio.h
+const GPIO_MAX_NAME_SIZE: usize = 32;
+const GPIO_V2_LINES_MAX: usize = 64;
+const GPIO_V2_LINE_NUM_ATTRS_MAX: usize = 10;
+
+const GPIO_V2_LINE_FLAG_INPUT: u64 = 1 << 2;
+const GPIO_V2_LINE_FLAG_OUTPUT: u64 = 1 << 3;
+
+const GPIO_V2_LINE_ATTR_ID_OUTPUT_VALUES: u32 = 2;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct LineValues {
+    bits: u64,
+    mask: u64,
+}
+
+// the kernel's union of flags, values and debounce period, only values are used
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct LineAttribute {
+    id: u32,
+    padding: u32,
+    values: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct LineConfigAttribute {
+    attr: LineAttribute,
+    mask: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct LineConfig {
+    flags: u64,
+    num_attrs: u32,
+    padding: [u32; 5],
+    attrs: [LineConfigAttribute; GPIO_V2_LINE_NUM_ATTRS_MAX],
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct LineRequest {
+    offsets: [u32; GPIO_V2_LINES_MAX],
+    consumer: [u8; GPIO_MAX_NAME_SIZE],
+    config: LineConfig,
+    num_lines: u32,
+    event_buffer_size: u32,
+    padding: [u32; 5],
+    fd: i32,
+}
+
+const _: () = assert!(mem::size_of::<LineValues>() == 16);
+const _: () = assert!(mem::size_of::<LineConfig>() == 272);
+const _: () = assert!(mem::size_of::<LineRequest>() == 592);
+
+// _IOWR(0xB4, nr, size)
+const fn iowr<T>(nr: u32) -> u32 {
+    3 << 30 | (mem::size_of::<T>() as u32) << 16 | 0xB4 << 8 | nr
+}
+
+const GPIO_V2_GET_LINE_IOCTL: u32 = iowr::<LineRequest>(0x07);
+const GPIO_V2_LINE_SET_CONFIG_IOCTL: u32 = iowr::<LineConfig>(0x0D);
+const GPIO_V2_LINE_GET_VALUES_IOCTL: u32 = iowr::<LineValues>(0x0E);
+const GPIO_V2_LINE_SET_VALUES_IOCTL: u32 = iowr::<LineValues>(0x0F);
+
+// shows up as the consumer of requested lines, e.g. in gpioinfo
+const CONSUMER: &[u8] = b"cps";
+
+unsafe fn ioctl<T>(fd: &impl AsRawFd, request: u32, arg: &mut T) -> io::Result<()> {
+    if libc::ioctl(fd.as_raw_fd(), request as _, arg as *mut T) < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+// a requested line, released when dropped
+#[derive(Debug)]
+struct Line {
+    fd: fd::OwnedFd,
+    mode: Option<GpioMode>,
+}
+
+impl Line {
+    fn config(flags: u64, level: Option<GpioLevel>) -> LineConfig {
+        // all zeroes is a valid, empty config
+        let mut config = unsafe { mem::zeroed::<LineConfig>() };
+        config.flags = flags;
+
+        if let Some(level) = level {
+            config.num_attrs = 1;
+            config.attrs[0] = LineConfigAttribute {
+                attr: LineAttribute {
+                    id: GPIO_V2_LINE_ATTR_ID_OUTPUT_VALUES,
+                    padding: 0,
+                    values: level as u64,
+                },
+                mask: 1,
+            };
+        }
+
+        config
+    }
+
+    fn set_mode(&mut self, mode: GpioMode) -> Result<()> {
+        let mut config = match mode {
+            GpioMode::Input => Self::config(GPIO_V2_LINE_FLAG_INPUT, None),
+            // keeps driving the level the pin is at
+            GpioMode::Output => Self::config(GPIO_V2_LINE_FLAG_OUTPUT, Some(self.read()?)),
+            // the character device has no say over the pin functions
+            _ => return Err(Error::Pi(ErrorCode::BadMode)),
+        };
+
+        unsafe { ioctl(&self.fd, GPIO_V2_LINE_SET_CONFIG_IOCTL, &mut config)? };
+        self.mode = Some(mode);
+        Ok(())
+    }
+
+    fn write(&self, level: GpioLevel) -> Result<()> {
+        let mut values = LineValues {
+            bits: level as u64,
+            mask: 1,
+        };
+
+        unsafe { ioctl(&self.fd, GPIO_V2_LINE_SET_VALUES_IOCTL, &mut values)? };
+        Ok(())
+    }
+
+    fn read(&self) -> Result<GpioLevel> {
+        let mut values = LineValues { bits: 0, mask: 1 };
+
+        unsafe { ioctl(&self.fd, GPIO_V2_LINE_GET_VALUES_IOCTL, &mut values)? };
+        Ok(GpioLevel::from(values.bits & 1 == 1))
+    }
+}
+
+#[derive(Debug)]
+pub struct GpioChip {
+    chip: fs::File,
+    // line offsets are the gpio numbers, lines are requested on first use
+    lines: sync::Mutex<collections::HashMap<Gpio, Line>>,
+    files: sync::Mutex<Files>,
+}
+
+#[derive(Debug, Default)]
+struct Files {
+    open: collections::HashMap<ffi::c_uint, fs::File>,
+    next_handle: ffi::c_uint,
+}
+
+impl GpioChip {
+    // e.g. /dev/gpiochip0, which carries gpios 0-53 on the Pi 4 and earlier
+    pub fn open<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<path::Path>,
+    {
+        let chip = fs::OpenOptions::new().read(true).write(true).open(path)?;
+
+        Ok(Self {
+            chip,
+            lines: sync::Mutex::default(),
+            files: sync::Mutex::default(),
+        })
+    }
+
+    fn with_line<T, F>(&self, gpio: Gpio, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Line) -> Result<T>,
+    {
+        let mut lines = lock(&self.lines);
+
+        let line = match lines.entry(gpio) {
+            collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            collections::hash_map::Entry::Vacant(entry) => entry.insert(self.request(gpio)?),
+        };
+
+        f(line)
+    }
+
+    // requested "as is", so the direction and level the pin has are kept
+    fn request(&self, gpio: Gpio) -> Result<Line> {
+        let mut request = unsafe { mem::zeroed::<LineRequest>() };
+        request.offsets[0] = gpio.get();
+        request.consumer[..CONSUMER.len()].copy_from_slice(CONSUMER);
+        request.config = Line::config(0, None);
+        request.num_lines = 1;
+
+        unsafe { ioctl(&self.chip, GPIO_V2_GET_LINE_IOCTL, &mut request)? };
+
+        Ok(Line {
+            fd: unsafe { fd::OwnedFd::from_raw_fd(request.fd) },
+            mode: None,
+        })
+    }
+
+    fn with_file<T, F>(&self, handle: &Handle, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut fs::File) -> io::Result<T>,
+    {
+        let mut files = lock(&self.files);
+        let file = files
+            .open
+            .get_mut(&handle.0)
+            .ok_or(Error::Pi(ErrorCode::BadHandle))?;
+
+        Ok(f(file)?)
+    }
+}
+
+fn lock<T>(mutex: &sync::Mutex<T>) -> sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(sync::PoisonError::into_inner)
+}
+
+impl GpioBackend for GpioChip {
+    fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()> {
+        self.with_line(gpio, |line| {
+            if line.mode == Some(mode) {
+                return Ok(());
+            }
+            line.set_mode(mode)
+        })
+    }
+
+    fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
+        self.with_line(gpio, |line| line.write(level))
+    }
+
+    fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        self.with_line(gpio, |line| line.read())
+    }
+
+    fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle> {
+        let mut options = fs::OpenOptions::new();
+        match mode {
+            FileMode::Read => options.read(true),
+            FileMode::Write => options.write(true),
+            FileMode::RW => options.read(true).write(true),
+            FileMode::Create => options.write(true).create(true).truncate(true),
+            FileMode::Append => options.append(true).create(true),
+        };
+        let file = options.open(path)?;
+
+        let mut files = lock(&self.files);
+        let handle = files.next_handle;
+        files.next_handle += 1;
+        files.open.insert(handle, file);
+        Ok(Handle(handle))
+    }
+
+    fn file_read(&self, handle: &Handle, buf: &mut [u8]) -> Result<usize> {
+        use io::Read;
+
+        self.with_file(handle, |file| file.read(buf))
+    }
+
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()> {
+        use io::Write;
+
+        self.with_file(handle, |file| file.write_all(buf))
+    }
+
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64> {
+        use io::Seek;
+
+        self.with_file(handle, |file| file.seek(pos))
+    }
+
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>> {
+        let pattern = path::Path::new(pattern);
+        let root = if pattern.is_absolute() {
+            path::PathBuf::from("/")
+        } else {
+            path::PathBuf::from(".")
+        };
+
+        let mut paths = vec![root];
+        for component in pattern.components() {
+            let path::Component::Normal(component) = component else {
+                continue;
+            };
+            let component = component.to_string_lossy();
+
+            paths = if component.contains(['*', '?']) {
+                paths
+                    .iter()
+                    .filter_map(|dir| fs::read_dir(dir).ok())
+                    .flatten()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| {
+                        let name = entry.file_name();
+                        backend::matches(component.as_bytes(), name.as_encoded_bytes())
+                    })
+                    .map(|entry| entry.path())
+                    .collect()
+            } else {
+                paths
+                    .iter()
+                    .map(|dir| dir.join(&*component))
+                    .filter(|path| path.exists())
+                    .collect()
+            };
+        }
+        paths.sort();
+
+        if paths.is_empty() {
+            return Err(ErrorCode::NoFileMatch.into());
+        }
+
+        Ok(paths)
+    }
+
+    fn file_close(&self, handle: &Handle) {
+        lock(&self.files).open.remove(&handle.0);
+    }
+}
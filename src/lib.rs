@@ -1,4 +1,14 @@
+pub mod backend;
+#[cfg(feature = "testing")]
+pub mod daemon;
+#[cfg(all(feature = "gpiochip", target_os = "linux"))]
+pub mod gpiochip;
+#[cfg(feature = "testing")]
+pub mod mock;
+pub mod model;
 pub mod pi;
 pub mod prelude;
+pub mod sampler;
+pub mod schema;
 pub mod segment_display;
 pub mod shift_register;
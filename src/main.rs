@@ -1,27 +1,39 @@
-use std::path::PathBuf;
+use std::sync;
+use std::thread;
+use std::time;
 
 use anyhow::Result;
 use clap::Parser;
-use cli::{Args, Format};
+use cli::{Args, Backend, Format};
 use cps::{
-    pi::{read_to_string, Pi},
-    segment_display::SegmentDisplay,
+    backend::GpioBackend,
+    pi::{OneWire, ReconnectingPi, Rom},
+    sampler::{sample, Misread, Sensor},
     shift_register::ShiftRegister,
 };
 use diesel::prelude::*;
-use model::NewTemperature;
 
 mod cli;
-mod model;
-mod schema;
+
+// before the next round after a failed one
+const RETRY: time::Duration = time::Duration::from_secs(1);
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = Args::parse().checked();
 
-    let pi = Pi::try_with_addr_and_port(&args.address, &args.port)?;
+    // the sensor shares the daemon connection with the display
+    let (pi, pigpiod): (Box<dyn GpioBackend>, _) = match args.backend {
+        Backend::Pigpiod => {
+            let pi = sync::Arc::new(ReconnectingPi::new(&args.address, &args.port)?);
+            (Box::new(pi.clone()), Some(pi))
+        }
+        #[cfg(all(feature = "gpiochip", target_os = "linux"))]
+        Backend::GpioChip => (Box::new(cps::gpiochip::GpioChip::open(&args.chip)?), None),
+    };
+    let pi = &*pi;
 
     let sh_reg = ShiftRegister::<4>::builder()
-        .pi(&pi)
+        .pi(pi)
         .ds(args.input)
         .sh_cp(args.shift)
         .st_cp(args.latch)
@@ -29,28 +41,25 @@ fn main() -> Result<()> {
 
     let mut conn = SqliteConnection::establish(&args.url)?;
 
-    let path = PathBuf::from("/sys/bus/w1/devices")
-        .join(&args.device)
-        .join("temperature");
+    let sensor = match (pigpiod, args.one_wire) {
+        (Some(pi), Some(gpio)) => {
+            Sensor::OneWire(OneWire::open(pi, gpio)?, args.device.parse::<Rom>()?)
+        }
+        _ => Sensor::w1(&args.device),
+    };
 
     let mut i = 0;
     while args.count.map(usize::from).map_or(true, |count| i < count) {
-        let temperature = read_to_string(&pi, &path)?
-            .chars()
-            .take_while(|&c| c != '\n')
-            .collect::<String>()
-            .parse::<f32>()?
-            / 1000.;
-
-        let width = 4 - temperature
-            .to_string()
-            .chars()
-            .take_while(|&c| c != '.')
-            .count();
-
-        sh_reg.write(format!("{temperature:.width$}"))?;
-
-        let row = NewTemperature::from(temperature).try_insert(&mut conn)?;
+        let row = match sample(pi, &sh_reg, &mut conn, &sensor) {
+            Ok(row) => row,
+            // the sensor is read again, everything else is for the user to fix
+            Err(err) if err.is::<Misread>() => {
+                eprintln!("skipping sample: {err:#}");
+                thread::sleep(RETRY);
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
 
         match args.format {
             Format::PlainText => println!("{row}"),
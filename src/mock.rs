@@ -0,0 +1,209 @@
+use std::collections;
+use std::ffi;
+use std::io;
+use std::path;
+use std::sync;
+
+use crate::backend;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Mode(Gpio, GpioMode),
+    Write(Gpio, GpioLevel),
+}
+
+#[derive(Debug, Default)]
+struct State {
+    modes: collections::HashMap<Gpio, GpioMode>,
+    levels: collections::HashMap<Gpio, GpioLevel>,
+    events: Vec<Event>,
+    files: collections::HashMap<path::PathBuf, Vec<u8>>,
+    // open files are written through, so they only keep a cursor
+    handles: collections::HashMap<ffi::c_uint, OpenFile>,
+    next_handle: ffi::c_uint,
+}
+
+#[derive(Debug)]
+struct OpenFile {
+    path: path::PathBuf,
+    mode: FileMode,
+    pos: usize,
+}
+
+// in-memory backend that records every mode change and pin write
+#[derive(Debug, Default)]
+pub struct Mock(sync::Mutex<State>);
+
+impl Mock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_file<P, C>(self, path: P, contents: C) -> Self
+    where
+        P: Into<path::PathBuf>,
+        C: Into<Vec<u8>>,
+    {
+        self.set_file(path, contents);
+        self
+    }
+
+    pub fn set_file<P, C>(&self, path: P, contents: C)
+    where
+        P: Into<path::PathBuf>,
+        C: Into<Vec<u8>>,
+    {
+        self.state().files.insert(path.into(), contents.into());
+    }
+
+    // drives a pin from the outside, e.g. to simulate an input
+    pub fn set_level(&self, gpio: Gpio, level: GpioLevel) {
+        self.state().levels.insert(gpio, level);
+    }
+
+    pub fn mode(&self, gpio: Gpio) -> Option<GpioMode> {
+        self.state().modes.get(&gpio).copied()
+    }
+
+    pub fn level(&self, gpio: Gpio) -> Option<GpioLevel> {
+        self.state().levels.get(&gpio).copied()
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.state().events.clone()
+    }
+
+    pub fn take_events(&self) -> Vec<Event> {
+        std::mem::take(&mut self.state().events)
+    }
+
+    fn state(&self) -> sync::MutexGuard<'_, State> {
+        self.0.lock().unwrap_or_else(sync::PoisonError::into_inner)
+    }
+}
+
+impl GpioBackend for Mock {
+    fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()> {
+        let mut state = self.state();
+        state.modes.insert(gpio, mode);
+        state.events.push(Event::Mode(gpio, mode));
+        Ok(())
+    }
+
+    fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
+        let mut state = self.state();
+        state.levels.insert(gpio, level);
+        state.events.push(Event::Write(gpio, level));
+        Ok(())
+    }
+
+    fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        Ok(self.level(gpio).unwrap_or(GpioLevel::Low))
+    }
+
+    fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle> {
+        let mut state = self.state();
+        match mode {
+            FileMode::Create => {
+                state.files.insert(path.to_owned(), Vec::new());
+            }
+            FileMode::Append => {
+                state.files.entry(path.to_owned()).or_default();
+            }
+            FileMode::Read | FileMode::Write | FileMode::RW => {}
+        }
+        if !state.files.contains_key(path) {
+            return Err(io::Error::from(io::ErrorKind::NotFound).into());
+        }
+
+        let handle = state.next_handle;
+        state.next_handle += 1;
+        state.handles.insert(
+            handle,
+            OpenFile {
+                path: path.to_owned(),
+                mode,
+                pos: 0,
+            },
+        );
+        Ok(Handle(handle))
+    }
+
+    fn file_read(&self, handle: &Handle, buf: &mut [u8]) -> Result<usize> {
+        let State { files, handles, .. } = &mut *self.state();
+        let file = handles
+            .get_mut(&handle.0)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+        let contents = files.get(&file.path).map_or(&[][..], Vec::as_slice);
+
+        let pos = file.pos.min(contents.len());
+        let count = buf.len().min(contents.len() - pos);
+        buf[..count].copy_from_slice(&contents[pos..pos + count]);
+        file.pos = pos + count;
+        Ok(count)
+    }
+
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()> {
+        let State { files, handles, .. } = &mut *self.state();
+        let file = handles
+            .get_mut(&handle.0)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+        if let FileMode::Read = file.mode {
+            return Err(ErrorCode::FileNotWopen.into());
+        }
+        let contents = files.entry(file.path.clone()).or_default();
+
+        if let FileMode::Append = file.mode {
+            file.pos = contents.len();
+        }
+        let end = file.pos + buf.len();
+        if contents.len() < end {
+            contents.resize(end, 0);
+        }
+        contents[file.pos..end].copy_from_slice(buf);
+        file.pos = end;
+        Ok(())
+    }
+
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64> {
+        let State { files, handles, .. } = &mut *self.state();
+        let file = handles
+            .get_mut(&handle.0)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+        let len = files.get(&file.path).map_or(0, Vec::len);
+
+        let pos = match pos {
+            io::SeekFrom::Start(offset) => i64::try_from(offset).ok(),
+            io::SeekFrom::Current(offset) => (file.pos as i64).checked_add(offset),
+            io::SeekFrom::End(offset) => (len as i64).checked_add(offset),
+        };
+        let pos = pos
+            .and_then(|pos| usize::try_from(pos).ok())
+            .ok_or(ErrorCode::BadFileSeek)?;
+
+        file.pos = pos;
+        Ok(pos as u64)
+    }
+
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>> {
+        let mut paths = self
+            .state()
+            .files
+            .keys()
+            .filter(|path| backend::matches(pattern.as_bytes(), path.to_string_lossy().as_bytes()))
+            .cloned()
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        if paths.is_empty() {
+            return Err(ErrorCode::NoFileMatch.into());
+        }
+
+        Ok(paths)
+    }
+
+    fn file_close(&self, handle: &Handle) {
+        self.state().handles.remove(&handle.0);
+    }
+}
@@ -4,14 +4,79 @@ use std::ffi;
 use std::fmt;
 use std::io;
 use std::num;
+use std::ops;
 use std::path;
 use std::ptr;
 use std::result;
 use std::str;
+use std::sync;
+use std::time;
+
+use crate::backend::GpioBackend;
+
+#[cfg(not(any(feature = "ffi", feature = "socket")))]
+compile_error!("either the `ffi` or the `socket` feature has to be enabled");
+
+#[cfg(feature = "tokio")]
+mod async_pi;
+mod bank;
+mod bit_bang;
+mod bsc;
+mod callback;
+mod clock;
+mod error_code;
+mod event;
+mod i2c;
+mod notify;
+mod one_wire;
+mod pin;
+pub(crate) mod protocol;
+mod pwm;
+mod reconnect;
+mod script;
+mod serial;
+#[cfg(feature = "socket")]
+mod socket;
+mod spi;
+mod system;
+mod wave;
+
+#[cfg(feature = "tokio")]
+pub use async_pi::*;
+pub use bank::*;
+pub use bit_bang::*;
+pub use bsc::*;
+pub use callback::*;
+pub use clock::*;
+pub use error_code::*;
+pub use event::*;
+pub use i2c::*;
+pub use notify::*;
+pub use one_wire::*;
+pub use pin::*;
+pub use pwm::*;
+pub use reconnect::*;
+pub use script::*;
+pub use serial::*;
+pub use spi::*;
+pub use system::*;
+pub use wave::*;
+
+// the native client takes precedence when both are enabled
+#[cfg(not(feature = "socket"))]
+use ::pigpiod_if2;
+#[cfg(feature = "socket")]
+use socket as pigpiod_if2;
 
 #[derive(Debug)]
 pub enum Error {
-    Pi(&'static ffi::CStr),
+    Pi(ErrorCode),
+    // negative return values this crate doesn't know, e.g. from a newer daemon
+    Unknown(ffi::c_int),
+    Io(io::Error),
+    Nul(ffi::NulError),
+    ParseInt(num::ParseIntError),
+    TryFromSlice(array::TryFromSliceError),
     Other(Box<dyn error::Error + Send + Sync + 'static>),
 }
 
@@ -19,8 +84,10 @@ pub type Result<T, E = Error> = result::Result<T, E>;
 
 impl Error {
     pub fn new(errnum: ffi::c_int) -> Self {
-        let err = unsafe { ffi::CStr::from_ptr(pigpiod_if2::pigpio_error(errnum)) };
-        Self::Pi(err)
+        match ErrorCode::from_code(errnum) {
+            Some(code) => Self::Pi(code),
+            None => Self::Unknown(errnum),
+        }
     }
 
     pub fn other<E>(error: E) -> Self
@@ -29,12 +96,24 @@ impl Error {
     {
         Self::Other(error.into())
     }
+
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::Pi(code) => Some(*code),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Pi(error) => error.to_bytes().escape_ascii().fmt(f),
+            Self::Pi(code) => code.fmt(f),
+            Self::Unknown(errnum) => write!(f, "unknown error {errnum}"),
+            Self::Io(error) => error.fmt(f),
+            Self::Nul(error) => error.fmt(f),
+            Self::ParseInt(error) => error.fmt(f),
+            Self::TryFromSlice(error) => error.fmt(f),
             Self::Other(error) => error.fmt(f),
         }
     }
@@ -43,39 +122,52 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
+            Self::Io(error) => Some(error),
+            Self::Nul(error) => Some(error),
+            Self::ParseInt(error) => Some(error),
+            Self::TryFromSlice(error) => Some(error),
             Self::Other(error) => Some(error.as_ref()),
             _ => None,
         }
     }
 }
 
+impl From<ErrorCode> for Error {
+    fn from(code: ErrorCode) -> Self {
+        Self::Pi(code)
+    }
+}
+
 impl From<ffi::NulError> for Error {
     fn from(error: ffi::NulError) -> Self {
-        Self::other(error)
+        Self::Nul(error)
     }
 }
 
 impl From<Error> for io::Error {
     fn from(error: Error) -> Self {
-        Self::other(error)
+        match error {
+            Error::Io(error) => error,
+            error => Self::other(error),
+        }
     }
 }
 
 impl From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
-        Self::other(error)
+        Self::Io(error)
     }
 }
 
 impl From<array::TryFromSliceError> for Error {
     fn from(error: array::TryFromSliceError) -> Self {
-        Self::other(error)
+        Self::TryFromSlice(error)
     }
 }
 
 impl From<num::ParseIntError> for Error {
     fn from(error: num::ParseIntError) -> Self {
-        Self::other(error)
+        Self::ParseInt(error)
     }
 }
 
@@ -91,6 +183,79 @@ pub struct Init(ffi::c_int);
 #[derive(Debug)]
 pub struct Pi<T>(T);
 
+// pigpiod_if2 serialises the commands on a connection, so it can be used from any thread
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Pi<Init>>();
+};
+
+// owned handle to hand out to long-lived registers, files and threads
+pub type SharedPi = sync::Arc<Pi<Init>>;
+
+// what the peripherals talk to the daemon through, a `Pi` itself, a
+// reference, a `SharedPi` or a `ReconnectingPi`
+pub trait PiConnection {
+    type Guard<'a>: ops::Deref<Target = Pi<Init>>
+    where
+        Self: 'a;
+
+    // the connection as it is right now
+    fn pi(&self) -> Self::Guard<'_>;
+
+    // runs `f` on the connection, what happens when it's lost is up to the
+    // implementation
+    fn with<T, F>(&self, mut f: F) -> Result<T>
+    where
+        F: FnMut(&Pi<Init>) -> Result<T>,
+    {
+        f(&self.pi())
+    }
+}
+
+impl PiConnection for Pi<Init> {
+    type Guard<'a> = &'a Pi<Init>;
+
+    fn pi(&self) -> &Pi<Init> {
+        self
+    }
+}
+
+impl<C: PiConnection + ?Sized> PiConnection for &C {
+    type Guard<'a>
+        = C::Guard<'a>
+    where
+        Self: 'a;
+
+    fn pi(&self) -> C::Guard<'_> {
+        (**self).pi()
+    }
+
+    fn with<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnMut(&Pi<Init>) -> Result<T>,
+    {
+        (**self).with(f)
+    }
+}
+
+impl<C: PiConnection + ?Sized> PiConnection for sync::Arc<C> {
+    type Guard<'a>
+        = C::Guard<'a>
+    where
+        Self: 'a;
+
+    fn pi(&self) -> C::Guard<'_> {
+        (**self).pi()
+    }
+
+    fn with<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnMut(&Pi<Init>) -> Result<T>,
+    {
+        (**self).with(f)
+    }
+}
+
 impl Pi<Uninit<NoAddr, NoPort>> {
     pub fn new() -> Self {
         Pi(Uninit {
@@ -176,7 +341,7 @@ impl Pi<Uninit<&str, &str>> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Gpio(ffi::c_uint);
 
 impl Gpio {
@@ -188,6 +353,10 @@ impl Gpio {
 
         Some(Gpio(gpio))
     }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
 }
 
 // needed for command line argument parsing
@@ -196,23 +365,65 @@ impl str::FromStr for Gpio {
 
     fn from_str(s: &str) -> Result<Self> {
         let raw = s.parse::<ffi::c_uint>()?;
-        let gpio = Gpio::new(raw).ok_or(Error::new(pigpiod_if2::PI_BAD_GPIO))?;
+        let gpio = Gpio::new(raw).ok_or(Error::Pi(ErrorCode::BadGpio))?;
         Ok(gpio)
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GpioMode {
     Input = pigpiod_if2::PI_INPUT as isize,
     Output = pigpiod_if2::PI_OUTPUT as isize,
+    // alternative functions, which one is what depends on the pin
+    Alt0 = pigpiod_if2::PI_ALT0 as isize,
+    Alt1 = pigpiod_if2::PI_ALT1 as isize,
+    Alt2 = pigpiod_if2::PI_ALT2 as isize,
+    Alt3 = pigpiod_if2::PI_ALT3 as isize,
+    Alt4 = pigpiod_if2::PI_ALT4 as isize,
+    Alt5 = pigpiod_if2::PI_ALT5 as isize,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl GpioMode {
+    fn from_raw(mode: ffi::c_uint) -> Option<Self> {
+        let mode = match mode {
+            pigpiod_if2::PI_INPUT => Self::Input,
+            pigpiod_if2::PI_OUTPUT => Self::Output,
+            pigpiod_if2::PI_ALT0 => Self::Alt0,
+            pigpiod_if2::PI_ALT1 => Self::Alt1,
+            pigpiod_if2::PI_ALT2 => Self::Alt2,
+            pigpiod_if2::PI_ALT3 => Self::Alt3,
+            pigpiod_if2::PI_ALT4 => Self::Alt4,
+            pigpiod_if2::PI_ALT5 => Self::Alt5,
+            _ => return None,
+        };
+
+        Some(mode)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GpioPud {
+    Off = pigpiod_if2::PI_PUD_OFF as isize,
+    Down = pigpiod_if2::PI_PUD_DOWN as isize,
+    Up = pigpiod_if2::PI_PUD_UP as isize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GpioLevel {
     Low = pigpiod_if2::PI_LOW as isize,
     High = pigpiod_if2::PI_HIGH as isize,
 }
 
+impl From<bool> for GpioLevel {
+    fn from(high: bool) -> Self {
+        if high {
+            Self::High
+        } else {
+            Self::Low
+        }
+    }
+}
+
 impl Pi<Init> {
     pub fn try_with_addr(addr: &str) -> Result<Self> {
         Pi::new().addr(addr).connect()
@@ -232,6 +443,26 @@ impl Pi<Init> {
         Ok(())
     }
 
+    pub fn get_mode(&self, gpio: Gpio) -> Result<GpioMode> {
+        let mode = unsafe { pigpiod_if2::get_mode(self.0 .0, gpio.0) };
+
+        if mode.is_negative() {
+            return Err(Error::new(mode));
+        }
+
+        GpioMode::from_raw(mode as ffi::c_uint).ok_or(Error::Pi(ErrorCode::BadMode))
+    }
+
+    pub fn set_pull_up_down(&self, gpio: Gpio, pud: GpioPud) -> Result<()> {
+        let err = unsafe { pigpiod_if2::set_pull_up_down(self.0 .0, gpio.0, pud as ffi::c_uint) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
     pub fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
         let err = unsafe { pigpiod_if2::gpio_write(self.0 .0, gpio.0, level as ffi::c_uint) };
 
@@ -242,6 +473,71 @@ impl Pi<Init> {
         Ok(())
     }
 
+    pub fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        let level = unsafe { pigpiod_if2::gpio_read(self.0 .0, gpio.0) };
+
+        if level.is_negative() {
+            return Err(Error::new(level));
+        }
+
+        Ok(GpioLevel::from(level as ffi::c_uint != pigpiod_if2::PI_LOW))
+    }
+
+    // reports a timeout to the callbacks on `gpio` if it doesn't change for
+    // `timeout` (at most a minute, in milliseconds), `None` switches it off
+    pub fn set_watchdog(&self, gpio: Gpio, timeout: Option<time::Duration>) -> Result<()> {
+        let timeout = match timeout {
+            Some(timeout) => ffi::c_uint::try_from(timeout.as_millis())
+                .ok()
+                .filter(|&ms| ms > 0 && ms <= pigpiod_if2::PI_MAX_WDOG_TIMEOUT)
+                .ok_or(Error::Pi(ErrorCode::BadWdogTimeout))?,
+            None => 0,
+        };
+
+        let err = unsafe { pigpiod_if2::set_watchdog(self.0 .0, gpio.0, timeout) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // a new level is only reported once it has been stable for `steady`,
+    // zero switches the filter off
+    pub fn set_glitch_filter(&self, gpio: Gpio, steady: time::Duration) -> Result<()> {
+        let steady = micros(steady, pigpiod_if2::PI_MAX_STEADY)?;
+
+        let err = unsafe { pigpiod_if2::set_glitch_filter(self.0 .0, gpio.0, steady) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // once a level has been stable for `steady`, changes are reported for
+    // `active` before waiting for the next stable level, zero `steady`
+    // switches the filter off
+    pub fn set_noise_filter(
+        &self,
+        gpio: Gpio,
+        steady: time::Duration,
+        active: time::Duration,
+    ) -> Result<()> {
+        let steady = micros(steady, pigpiod_if2::PI_MAX_STEADY)?;
+        let active = micros(active, pigpiod_if2::PI_MAX_ACTIVE)?;
+
+        let err = unsafe { pigpiod_if2::set_noise_filter(self.0 .0, gpio.0, steady, active) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
     fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle> {
         let filestr = ffi::CString::new(path.to_string_lossy().as_bytes())?;
         let pfile = filestr.as_ptr().cast_mut();
@@ -268,11 +564,120 @@ impl Pi<Init> {
         Ok(count as usize)
     }
 
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()> {
+        let pbuf = buf.as_ptr().cast_mut().cast();
+        let buflen = buf.len() as ffi::c_uint;
+
+        let err = unsafe { pigpiod_if2::file_write(self.0 .0, handle.0, pbuf, buflen) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64> {
+        let (offset, from) = match pos {
+            io::SeekFrom::Start(offset) => (i32::try_from(offset).ok(), pigpiod_if2::PI_FROM_START),
+            io::SeekFrom::Current(offset) => {
+                (i32::try_from(offset).ok(), pigpiod_if2::PI_FROM_CURRENT)
+            }
+            io::SeekFrom::End(offset) => (i32::try_from(offset).ok(), pigpiod_if2::PI_FROM_END),
+        };
+        // the daemon only takes 32 bit offsets
+        let offset = offset.ok_or(ErrorCode::BadFileSeek)?;
+
+        let pos =
+            unsafe { pigpiod_if2::file_seek(self.0 .0, handle.0, offset, from as ffi::c_int) };
+
+        if pos.is_negative() {
+            return Err(Error::new(pos));
+        }
+
+        Ok(pos as u64)
+    }
+
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>> {
+        let patstr = ffi::CString::new(pattern)?;
+        let ppat = patstr.as_ptr().cast_mut();
+        let mut buf = vec![0u8; FILE_LIST_LEN];
+
+        let count = unsafe {
+            pigpiod_if2::file_list(
+                self.0 .0,
+                ppat,
+                buf.as_mut_ptr().cast(),
+                buf.len() as ffi::c_uint,
+            )
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        buf.truncate(count as usize);
+        Ok(parse_file_list(&buf))
+    }
+
     fn file_close(&self, handle: &Handle) {
         unsafe { pigpiod_if2::file_close(self.0 .0, handle.0) };
     }
 }
 
+fn micros(duration: time::Duration, max: ffi::c_uint) -> Result<ffi::c_uint> {
+    ffi::c_uint::try_from(duration.as_micros())
+        .ok()
+        .filter(|&us| us <= max)
+        .ok_or(Error::Pi(ErrorCode::BadFilter))
+}
+
+impl GpioBackend for Pi<Init> {
+    fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()> {
+        Pi::set_mode(self, gpio, mode)
+    }
+
+    fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
+        Pi::gpio_write(self, gpio, level)
+    }
+
+    fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        Pi::gpio_read(self, gpio)
+    }
+
+    fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle> {
+        Pi::file_open(self, path, mode)
+    }
+
+    fn file_read(&self, handle: &Handle, buf: &mut [u8]) -> Result<usize> {
+        Pi::file_read(self, handle, buf)
+    }
+
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()> {
+        Pi::file_write(self, handle, buf)
+    }
+
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64> {
+        Pi::file_seek(self, handle, pos)
+    }
+
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>> {
+        Pi::file_list(self, pattern)
+    }
+
+    fn file_close(&self, handle: &Handle) {
+        Pi::file_close(self, handle)
+    }
+
+    fn send_pulses(&self, pulses: &[Pulse]) -> Result<()> {
+        Pi::send_pulses(self, pulses)
+    }
+
+    fn current_tick(&self) -> Result<u32> {
+        Ok(Pi::current_tick(self)?.get())
+    }
+}
+
 impl Drop for Init {
     fn drop(&mut self) {
         unsafe { pigpiod_if2::pigpio_stop(self.0) }
@@ -280,10 +685,22 @@ impl Drop for Init {
 }
 
 #[derive(Debug)]
-struct Handle(ffi::c_uint);
+pub struct Handle(pub(crate) ffi::c_uint);
+
+impl Handle {
+    // for backends outside this crate, whatever identifies their open files
+    pub fn new(handle: ffi::c_uint) -> Self {
+        Handle(handle)
+    }
+
+    pub fn get(&self) -> ffi::c_uint {
+        self.0
+    }
+}
+
 #[derive(Debug)]
-pub struct File<'a> {
-    pi: &'a Pi<Init>,
+pub struct File<B: GpioBackend = Pi<Init>> {
+    pi: B,
     handle: Handle,
 }
 
@@ -292,10 +709,30 @@ pub enum FileMode {
     Read = pigpiod_if2::PI_FILE_READ as isize,
     Write = pigpiod_if2::PI_FILE_WRITE as isize,
     RW = pigpiod_if2::PI_FILE_RW as isize,
+    // write only, created if missing and truncated otherwise
+    Create = (pigpiod_if2::PI_FILE_WRITE | pigpiod_if2::PI_FILE_CREATE | pigpiod_if2::PI_FILE_TRUNC)
+        as isize,
+    // write only, created if missing, every write goes to the end
+    Append = (pigpiod_if2::PI_FILE_WRITE
+        | pigpiod_if2::PI_FILE_CREATE
+        | pigpiod_if2::PI_FILE_APPEND) as isize,
 }
 
-impl<'a> File<'a> {
-    pub fn open<P>(pi: &'a Pi<Init>, path: P, mode: FileMode) -> Result<Self>
+// same size the C library asks the daemon for
+const FILE_LIST_LEN: usize = 60000;
+
+// the daemon terminates every name with a newline
+pub(crate) fn parse_file_list(buf: &[u8]) -> Vec<path::PathBuf> {
+    String::from_utf8_lossy(buf)
+        .lines()
+        .filter(|name| !name.is_empty())
+        .map(path::PathBuf::from)
+        .collect()
+}
+
+impl<B: GpioBackend> File<B> {
+    // takes a reference or an owned handle like `SharedPi`
+    pub fn open<P>(pi: B, path: P, mode: FileMode) -> Result<Self>
     where
         P: AsRef<path::Path>,
     {
@@ -306,20 +743,39 @@ impl<'a> File<'a> {
     pub fn close(self) {}
 }
 
-impl<'a> io::Read for File<'a> {
+impl<B: GpioBackend> io::Read for File<B> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         Ok(self.pi.file_read(&self.handle, buf)?)
     }
 }
 
-impl<'a> Drop for File<'a> {
+impl<B: GpioBackend> io::Write for File<B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pi.file_write(&self.handle, buf)?;
+        Ok(buf.len())
+    }
+
+    // the daemon writes straight through
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<B: GpioBackend> io::Seek for File<B> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        Ok(self.pi.file_seek(&self.handle, pos)?)
+    }
+}
+
+impl<B: GpioBackend> Drop for File<B> {
     fn drop(&mut self) {
         self.pi.file_close(&self.handle);
     }
 }
 
-pub fn read_to_string<P>(pi: &Pi<Init>, path: P) -> Result<String>
+pub fn read_to_string<B, P>(pi: &B, path: P) -> Result<String>
 where
+    B: GpioBackend,
     P: AsRef<path::Path>,
 {
     use io::Read;
@@ -329,3 +785,11 @@ where
     file.read_to_string(&mut buf)?;
     Ok(buf)
 }
+
+// `pattern` may contain wildcards, e.g. "/sys/bus/w1/devices/28-*"
+pub fn list_files<B>(pi: &B, pattern: &str) -> Result<Vec<path::PathBuf>>
+where
+    B: GpioBackend,
+{
+    pi.file_list(pattern)
+}
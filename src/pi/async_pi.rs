@@ -0,0 +1,338 @@
+use std::future;
+use std::net;
+use std::path;
+use std::pin;
+use std::sync;
+use std::task;
+
+use tokio::io;
+
+use super::protocol::*;
+use super::{Edge, Error, ErrorCode, FileMode, Gpio, GpioLevel, GpioMode, GpioPud, Result};
+
+// talks to pigpiod over its socket interface without blocking the runtime,
+// clones share the connection
+#[derive(Debug, Clone)]
+pub struct AsyncPi(sync::Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    addr: net::SocketAddr,
+    // taken out while a command is in flight and only put back once its
+    // response is read, a command that was cancelled or failed halfway
+    // leaves none and the next one connects again instead of reading a
+    // stale response
+    stream: tokio::sync::Mutex<Option<tokio::net::TcpStream>>,
+}
+
+impl AsyncPi {
+    pub async fn connect(addr: &str, port: &str) -> Result<Self> {
+        let port = port.parse::<u16>()?;
+        let stream = connect((addr, port)).await?;
+
+        Ok(AsyncPi(sync::Arc::new(Inner {
+            addr: stream.peer_addr()?,
+            stream: tokio::sync::Mutex::new(Some(stream)),
+        })))
+    }
+
+    pub async fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()> {
+        self.command(PI_CMD_MODES, gpio.0, mode as u32, &[]).await?;
+        Ok(())
+    }
+
+    pub async fn set_pull_up_down(&self, gpio: Gpio, pud: GpioPud) -> Result<()> {
+        self.command(PI_CMD_PUD, gpio.0, pud as u32, &[]).await?;
+        Ok(())
+    }
+
+    pub async fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
+        self.command(PI_CMD_WRITE, gpio.0, level as u32, &[])
+            .await?;
+        Ok(())
+    }
+
+    pub async fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        let level = self.command(PI_CMD_READ, gpio.0, 0, &[]).await?;
+        Ok(GpioLevel::from(level as u32 != PI_LOW))
+    }
+
+    pub async fn file_open<P>(&self, path: P, mode: FileMode) -> Result<AsyncFile>
+    where
+        P: AsRef<path::Path>,
+    {
+        let path = path.as_ref().to_string_lossy();
+        let handle = self
+            .command(PI_CMD_FO, mode as u32, 0, path.as_bytes())
+            .await?;
+
+        Ok(AsyncFile {
+            pi: self.clone(),
+            handle: Some(handle as u32),
+        })
+    }
+
+    pub async fn read_to_string<P>(&self, path: P) -> Result<String>
+    where
+        P: AsRef<path::Path>,
+    {
+        let mut file = self.file_open(path, FileMode::Read).await?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).await?;
+        file.close().await?;
+
+        String::from_utf8(buf).map_err(Error::other)
+    }
+
+    // level changes of `gpio` as they are reported by the daemon
+    pub async fn edges(&self, gpio: Gpio, edge: Edge) -> Result<Edges> {
+        use io::{AsyncReadExt, AsyncWriteExt};
+
+        if gpio.0 > PI_MAX_USER_GPIO {
+            return Err(ErrorCode::BadUserGpio.into());
+        }
+
+        // every stream gets its own notification socket
+        let mut stream = connect(self.0.addr).await?;
+
+        let request = Header::new(PI_CMD_NOIB, 0, 0, 0);
+        stream
+            .write_all(&request.to_bytes())
+            .await
+            .map_err(|_| ErrorCode::BadSend)?;
+        let mut response = [0; Header::LEN];
+        stream
+            .read_exact(&mut response)
+            .await
+            .map_err(|_| ErrorCode::BadRecv)?;
+        let handle = Header::from_bytes(response).res();
+        if handle.is_negative() {
+            return Err(ErrorCode::BadNoib.into());
+        }
+
+        let edges = Edges {
+            pi: self.clone(),
+            stream,
+            handle: handle as u32,
+            gpio,
+            edge,
+            last: self.command(PI_CMD_BR1, 0, 0, &[]).await? as u32,
+            buf: [0; Report::LEN],
+            filled: 0,
+        };
+        self.command(PI_CMD_NB, edges.handle, 1 << gpio.0, &[])
+            .await?;
+
+        Ok(edges)
+    }
+
+    async fn command(&self, cmd: u32, p1: u32, p2: u32, ext: &[u8]) -> Result<i32> {
+        let (res, _) = self.request(cmd, p1, p2, ext, false).await?;
+        Ok(res)
+    }
+
+    // extended responses carry `res` bytes after the header
+    async fn request(
+        &self,
+        cmd: u32,
+        p1: u32,
+        p2: u32,
+        ext: &[u8],
+        extended: bool,
+    ) -> Result<(i32, Vec<u8>)> {
+        use io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut slot = self.0.stream.lock().await;
+        let mut stream = match slot.take() {
+            Some(stream) => stream,
+            None => connect(self.0.addr).await?,
+        };
+
+        let mut buf = Vec::with_capacity(Header::LEN + ext.len());
+        buf.extend_from_slice(&Header::new(cmd, p1, p2, ext.len() as u32).to_bytes());
+        buf.extend_from_slice(ext);
+        stream
+            .write_all(&buf)
+            .await
+            .map_err(|_| ErrorCode::BadSend)?;
+
+        let mut response = [0; Header::LEN];
+        stream
+            .read_exact(&mut response)
+            .await
+            .map_err(|_| ErrorCode::BadRecv)?;
+        let res = Header::from_bytes(response).res();
+
+        let mut data = Vec::new();
+        if extended && res > 0 {
+            data.resize(res as usize, 0);
+            stream
+                .read_exact(&mut data)
+                .await
+                .map_err(|_| ErrorCode::BadRecv)?;
+        }
+
+        *slot = Some(stream);
+
+        if res.is_negative() {
+            return Err(Error::new(res));
+        }
+
+        Ok((res, data))
+    }
+
+    // drop can't wait, so the daemon is told in the background
+    fn spawn_command(&self, cmd: u32, p1: u32) {
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let pi = self.clone();
+            runtime.spawn(async move {
+                let _ = pi.command(cmd, p1, 0, &[]).await;
+            });
+        }
+    }
+}
+
+async fn connect<A: tokio::net::ToSocketAddrs>(addr: A) -> Result<tokio::net::TcpStream> {
+    let stream = tokio::net::TcpStream::connect(addr)
+        .await
+        .map_err(|_| ErrorCode::BadConnect)?;
+    stream.set_nodelay(true)?;
+    Ok(stream)
+}
+
+// closed when dropped, `close` reports errors
+#[derive(Debug)]
+pub struct AsyncFile {
+    pi: AsyncPi,
+    handle: Option<u32>,
+}
+
+impl AsyncFile {
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let handle = self.handle.ok_or(ErrorCode::BadHandle)?;
+
+        let (count, data) = self
+            .pi
+            .request(PI_CMD_FR, handle, buf.len() as u32, &[], true)
+            .await?;
+        let count = (count as usize).min(data.len()).min(buf.len());
+        buf[..count].copy_from_slice(&data[..count]);
+
+        Ok(count)
+    }
+
+    pub async fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        let mut chunk = [0; 4096];
+        let mut total = 0;
+
+        loop {
+            let count = self.read(&mut chunk).await?;
+            if count == 0 {
+                return Ok(total);
+            }
+            buf.extend_from_slice(&chunk[..count]);
+            total += count;
+        }
+    }
+
+    pub async fn close(mut self) -> Result<()> {
+        if let Some(handle) = self.handle.take() {
+            self.pi.command(PI_CMD_FC, handle, 0, &[]).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for AsyncFile {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            self.pi.spawn_command(PI_CMD_FC, handle);
+        }
+    }
+}
+
+// yields the new level and the tick of every matching change,
+// the notification is closed when dropped
+#[derive(Debug)]
+pub struct Edges {
+    pi: AsyncPi,
+    stream: tokio::net::TcpStream,
+    handle: u32,
+    gpio: Gpio,
+    edge: Edge,
+    last: u32,
+    // reports can arrive in pieces
+    buf: [u8; Report::LEN],
+    filled: usize,
+}
+
+impl Edges {
+    pub async fn next(&mut self) -> Option<Result<(GpioLevel, u32)>> {
+        future::poll_fn(|cx| pin::Pin::new(&mut *self).poll_report(cx)).await
+    }
+
+    fn poll_report(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Result<(GpioLevel, u32)>>> {
+        use io::AsyncRead;
+
+        loop {
+            while self.filled < Report::LEN {
+                let mut buf = io::ReadBuf::new(&mut self.buf[self.filled..]);
+                match pin::Pin::new(&mut self.stream).poll_read(cx, &mut buf) {
+                    task::Poll::Pending => return task::Poll::Pending,
+                    task::Poll::Ready(Err(_)) => {
+                        return task::Poll::Ready(Some(Err(ErrorCode::BadRecv.into())))
+                    }
+                    task::Poll::Ready(Ok(())) if buf.filled().is_empty() => {
+                        return task::Poll::Ready(None)
+                    }
+                    task::Poll::Ready(Ok(())) => self.filled += buf.filled().len(),
+                }
+            }
+            self.filled = 0;
+
+            let report = Report::from_bytes(self.buf);
+            // watchdog timeouts, keep-alives and events
+            if report.flags != 0 {
+                continue;
+            }
+
+            let changed = report.level ^ self.last;
+            self.last = report.level;
+            if changed >> self.gpio.0 & 1 == 0 {
+                continue;
+            }
+
+            let level = GpioLevel::from(report.level >> self.gpio.0 & 1 == 1);
+            let wanted = match self.edge {
+                Edge::Rising => level == GpioLevel::High,
+                Edge::Falling => level == GpioLevel::Low,
+                Edge::Either => true,
+            };
+
+            if wanted {
+                return task::Poll::Ready(Some(Ok((level, report.tick))));
+            }
+        }
+    }
+}
+
+impl futures_core::Stream for Edges {
+    type Item = Result<(GpioLevel, u32)>;
+
+    fn poll_next(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        self.get_mut().poll_report(cx)
+    }
+}
+
+impl Drop for Edges {
+    fn drop(&mut self) {
+        self.pi.spawn_command(PI_CMD_NC, self.handle);
+    }
+}
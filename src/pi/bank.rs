@@ -0,0 +1,203 @@
+use std::ops;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Gpio, Init, Pi, Result};
+
+// set of gpios from bank 1, i.e. 0-31
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GpioMask(u32);
+
+impl GpioMask {
+    pub const EMPTY: Self = GpioMask(0);
+
+    pub fn new() -> Self {
+        Self::EMPTY
+    }
+
+    pub fn from_gpios<I>(gpios: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = Gpio>,
+    {
+        gpios
+            .into_iter()
+            .try_fold(Self::EMPTY, |mask, gpio| mask.with(gpio))
+    }
+
+    pub fn with(self, gpio: Gpio) -> Result<Self> {
+        if gpio.0 > pigpiod_if2::PI_MAX_USER_GPIO {
+            return Err(Error::Pi(ErrorCode::BadUserGpio));
+        }
+
+        Ok(GpioMask(self.0 | 1 << gpio.0))
+    }
+
+    pub fn contains(self, gpio: Gpio) -> bool {
+        gpio.0 <= pigpiod_if2::PI_MAX_USER_GPIO && self.0 >> gpio.0 & 1 == 1
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn gpios(self) -> impl Iterator<Item = Gpio> {
+        (0..=pigpiod_if2::PI_MAX_USER_GPIO)
+            .filter(move |bit| self.0 >> bit & 1 == 1)
+            .map(Gpio)
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+macro_rules! mask_ops {
+    ($mask:ident, $all:expr) => {
+        impl ops::BitOr for $mask {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                $mask(self.0 | rhs.0)
+            }
+        }
+
+        impl ops::BitAnd for $mask {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                $mask(self.0 & rhs.0)
+            }
+        }
+
+        impl ops::Not for $mask {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                $mask(!self.0 & $all)
+            }
+        }
+    };
+}
+
+mask_ops!(GpioMask, u32::MAX);
+
+// set of gpios from bank 2, i.e. 32-53, bit n is gpio 32 + n
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bank2Mask(u32);
+
+const BANK_2: u32 = pigpiod_if2::PI_MAX_USER_GPIO + 1;
+
+impl Bank2Mask {
+    pub const EMPTY: Self = Bank2Mask(0);
+
+    pub fn new() -> Self {
+        Self::EMPTY
+    }
+
+    pub fn from_gpios<I>(gpios: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = Gpio>,
+    {
+        gpios
+            .into_iter()
+            .try_fold(Self::EMPTY, |mask, gpio| mask.with(gpio))
+    }
+
+    pub fn with(self, gpio: Gpio) -> Result<Self> {
+        if !(BANK_2..=pigpiod_if2::PI_MAX_GPIO).contains(&gpio.0) {
+            return Err(Error::Pi(ErrorCode::BadGpio));
+        }
+
+        Ok(Bank2Mask(self.0 | 1 << (gpio.0 - BANK_2)))
+    }
+
+    pub fn contains(self, gpio: Gpio) -> bool {
+        (BANK_2..=pigpiod_if2::PI_MAX_GPIO).contains(&gpio.0)
+            && self.0 >> (gpio.0 - BANK_2) & 1 == 1
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn gpios(self) -> impl Iterator<Item = Gpio> {
+        (BANK_2..=pigpiod_if2::PI_MAX_GPIO)
+            .filter(move |gpio| self.0 >> (gpio - BANK_2) & 1 == 1)
+            .map(Gpio)
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+mask_ops!(
+    Bank2Mask,
+    (1 << (pigpiod_if2::PI_MAX_GPIO - BANK_2 + 1)) - 1
+);
+
+impl Pi<Init> {
+    // the gpios of bank 1 that are high, all sampled at once, the C library
+    // can't tell errors from levels, the socket client can
+    pub fn read_bank_1(&self) -> Result<GpioMask> {
+        #[cfg(feature = "socket")]
+        let bits = super::socket::command_u32(self.0 .0, super::protocol::PI_CMD_BR1)
+            .map_err(Error::new)?;
+        #[cfg(not(feature = "socket"))]
+        let bits = unsafe { pigpiod_if2::read_bank_1(self.0 .0) };
+
+        Ok(GpioMask(bits))
+    }
+
+    // switches every gpio in `mask` high in a single call
+    pub fn set_bank_1(&self, mask: GpioMask) -> Result<()> {
+        let err = unsafe { pigpiod_if2::set_bank_1(self.0 .0, mask.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // switches every gpio in `mask` low in a single call
+    pub fn clear_bank_1(&self, mask: GpioMask) -> Result<()> {
+        let err = unsafe { pigpiod_if2::clear_bank_1(self.0 .0, mask.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // like `read_bank_1`, for gpios 32-53
+    pub fn read_bank_2(&self) -> Result<Bank2Mask> {
+        #[cfg(feature = "socket")]
+        let bits = super::socket::command_u32(self.0 .0, super::protocol::PI_CMD_BR2)
+            .map_err(Error::new)?;
+        #[cfg(not(feature = "socket"))]
+        let bits = unsafe { pigpiod_if2::read_bank_2(self.0 .0) };
+
+        Ok(Bank2Mask(bits))
+    }
+
+    pub fn set_bank_2(&self, mask: Bank2Mask) -> Result<()> {
+        let err = unsafe { pigpiod_if2::set_bank_2(self.0 .0, mask.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn clear_bank_2(&self, mask: Bank2Mask) -> Result<()> {
+        let err = unsafe { pigpiod_if2::clear_bank_2(self.0 .0, mask.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+}
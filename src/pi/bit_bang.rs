@@ -0,0 +1,210 @@
+use std::ffi;
+
+use embedded_hal::i2c as hal;
+
+use super::i2c::{decode_segments, encode_segments};
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Gpio, Init, Pi, PiConnection, Result};
+
+// SPI clocked out by the daemon on any pins, closed when dropped
+#[derive(Debug)]
+pub struct BbSpi<C: PiConnection = Pi<Init>> {
+    pi: C,
+    cs: Gpio,
+}
+
+impl<C: PiConnection> BbSpi<C> {
+    // the chip select pin identifies the bus, `flags` is pigpio's spiFlags word
+    // of which only the mode, the CS polarity and the bit orders apply
+    pub fn open(
+        pi: C,
+        cs: Gpio,
+        miso: Gpio,
+        mosi: Gpio,
+        sclk: Gpio,
+        baud: ffi::c_uint,
+        flags: ffi::c_uint,
+    ) -> Result<Self> {
+        let err = unsafe {
+            pigpiod_if2::bb_spi_open(pi.pi().0 .0, cs.0, miso.0, mosi.0, sclk.0, baud, flags)
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(Self { pi, cs })
+    }
+
+    pub fn close(self) {}
+
+    // full duplex, `rx` has to be as long as `tx`
+    pub fn xfer(&self, tx: &[u8], rx: &mut [u8]) -> Result<usize> {
+        if tx.len() != rx.len() {
+            return Err(Error::Pi(ErrorCode::BadSpiCount));
+        }
+
+        let ptx = tx.as_ptr().cast_mut().cast();
+        let prx = rx.as_mut_ptr().cast();
+        let buflen = tx.len() as ffi::c_uint;
+
+        let count =
+            unsafe { pigpiod_if2::bb_spi_xfer(self.pi.pi().0 .0, self.cs.0, ptx, prx, buflen) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+}
+
+impl<C: PiConnection> Drop for BbSpi<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::bb_spi_close(self.pi.pi().0 .0, self.cs.0) };
+    }
+}
+
+// serial data sampled by the daemon on any user gpio, closed when dropped
+#[derive(Debug)]
+pub struct BbSerial<C: PiConnection = Pi<Init>> {
+    pi: C,
+    gpio: Gpio,
+}
+
+impl<C: PiConnection> BbSerial<C> {
+    // `data_bits` is 1-32, words wider than 8 bits take 2 or 4 bytes in the buffer
+    pub fn open(pi: C, gpio: Gpio, baud: ffi::c_uint, data_bits: ffi::c_uint) -> Result<Self> {
+        let err =
+            unsafe { pigpiod_if2::bb_serial_read_open(pi.pi().0 .0, gpio.0, baud, data_bits) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(Self { pi, gpio })
+    }
+
+    pub fn close(self) {}
+
+    // doesn't wait, 0 if nothing has been received
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let pbuf = buf.as_mut_ptr().cast();
+
+        let count =
+            unsafe { pigpiod_if2::bb_serial_read(self.pi.pi().0 .0, self.gpio.0, pbuf, buf.len()) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    // for lines that idle low
+    pub fn set_invert(&self, invert: bool) -> Result<()> {
+        let invert = if invert {
+            pigpiod_if2::PI_BB_SER_INVERT
+        } else {
+            pigpiod_if2::PI_BB_SER_NORMAL
+        };
+
+        let err = unsafe { pigpiod_if2::bb_serial_invert(self.pi.pi().0 .0, self.gpio.0, invert) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: PiConnection> Drop for BbSerial<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::bb_serial_read_close(self.pi.pi().0 .0, self.gpio.0) };
+    }
+}
+
+// I2C clocked out by the daemon on any pins, for buses without a kernel
+// driver, closed when dropped
+#[derive(Debug)]
+pub struct BbI2c<C: PiConnection = Pi<Init>> {
+    pi: C,
+    sda: Gpio,
+}
+
+impl<C: PiConnection> BbI2c<C> {
+    // the data pin identifies the bus, `baud` is 50-500000, both pins need
+    // pull-ups
+    pub fn open(pi: C, sda: Gpio, scl: Gpio, baud: ffi::c_uint) -> Result<Self> {
+        if !(pigpiod_if2::PI_BB_I2C_MIN_BAUD..=pigpiod_if2::PI_BB_I2C_MAX_BAUD).contains(&baud) {
+            return Err(Error::Pi(ErrorCode::BadI2cBaud));
+        }
+
+        let err = unsafe { pigpiod_if2::bb_i2c_open(pi.pi().0 .0, sda.0, scl.0, baud) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(Self { pi, sda })
+    }
+
+    pub fn close(self) {}
+
+    // runs pigpio's bbI2CZip command list, which addresses with PI_I2C_START
+    // and PI_I2C_STOP instead of the combined flag, returns how much of `out`
+    // was filled
+    pub fn zip(&self, cmds: &[u8], out: &mut [u8]) -> Result<usize> {
+        let pcmds = cmds.as_ptr().cast_mut().cast();
+        let pout = out.as_mut_ptr().cast();
+
+        let count = unsafe {
+            pigpiod_if2::bb_i2c_zip(
+                self.pi.pi().0 .0,
+                self.sda.0,
+                pcmds,
+                cmds.len() as ffi::c_uint,
+                pout,
+                out.len() as ffi::c_uint,
+            )
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+}
+
+impl<C: PiConnection> Drop for BbI2c<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::bb_i2c_close(self.pi.pi().0 .0, self.sda.0) };
+    }
+}
+
+impl<C: PiConnection> hal::ErrorType for BbI2c<C> {
+    type Error = Error;
+}
+
+impl<C: PiConnection> hal::I2c for BbI2c<C> {
+    fn transaction(&mut self, address: u8, operations: &mut [hal::Operation<'_>]) -> Result<()> {
+        // the daemon sends the address after every start, later ones are repeated starts
+        let mut cmds = vec![pigpiod_if2::PI_I2C_ADDR as u8, address];
+        let start = pigpiod_if2::PI_I2C_START as u8;
+        let read = encode_segments(&mut cmds, operations, Some(start))?;
+        cmds.extend([
+            pigpiod_if2::PI_I2C_STOP as u8,
+            pigpiod_if2::PI_I2C_END as u8,
+        ]);
+
+        let mut out = vec![0; read];
+        if self.zip(&cmds, &mut out)? != read {
+            return Err(Error::Pi(ErrorCode::I2cReadFailed));
+        }
+
+        decode_segments(&out, operations);
+        Ok(())
+    }
+}
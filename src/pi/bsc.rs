@@ -0,0 +1,84 @@
+use std::ffi;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Init, Pi, Result};
+
+// what a transfer on the BSC peripheral brought back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BscTransfer {
+    status: u32,
+    rx: Vec<u8>,
+}
+
+impl BscTransfer {
+    // the peripheral's status word, see pigpio's bscXfer for the bits
+    pub fn status(&self) -> u32 {
+        self.status
+    }
+
+    // what the master sent since the last transfer
+    pub fn rx(&self) -> &[u8] {
+        &self.rx
+    }
+}
+
+impl Pi<Init> {
+    // `control` is pigpio's BSC control word, `tx` (at most 512 bytes) is
+    // queued for the master to read
+    pub fn bsc_xfer(&self, control: u32, tx: &[u8]) -> Result<BscTransfer> {
+        let mut xfer = new_xfer(control, tx)?;
+
+        let status = unsafe { pigpiod_if2::bsc_xfer(self.0 .0, &mut xfer) };
+
+        if status.is_negative() {
+            return Err(Error::new(status));
+        }
+
+        Ok(transfer(status, &xfer))
+    }
+
+    // makes the Pi an I2C slave at `addr`, `None` switches it off again
+    pub fn bsc_i2c(&self, addr: Option<u8>, tx: &[u8]) -> Result<BscTransfer> {
+        let mut xfer = new_xfer(0, tx)?;
+        let addr = addr.map_or(0, ffi::c_int::from);
+
+        let status = unsafe { pigpiod_if2::bsc_i2c(self.0 .0, addr, &mut xfer) };
+
+        if status.is_negative() {
+            return Err(Error::new(status));
+        }
+
+        Ok(transfer(status, &xfer))
+    }
+}
+
+fn new_xfer(control: u32, tx: &[u8]) -> Result<pigpiod_if2::bsc_xfer_t> {
+    let mut xfer = pigpiod_if2::bsc_xfer_t {
+        control,
+        rxCnt: 0,
+        rxBuf: [0; pigpiod_if2::BSC_FIFO_SIZE as usize],
+        txCnt: tx.len() as ffi::c_int,
+        txBuf: [0; pigpiod_if2::BSC_FIFO_SIZE as usize],
+    };
+
+    let txbuf = xfer
+        .txBuf
+        .get_mut(..tx.len())
+        .ok_or(Error::Pi(ErrorCode::BadParam))?;
+    for (dst, src) in txbuf.iter_mut().zip(tx) {
+        *dst = *src as ffi::c_char;
+    }
+
+    Ok(xfer)
+}
+
+fn transfer(status: ffi::c_int, xfer: &pigpiod_if2::bsc_xfer_t) -> BscTransfer {
+    let count = usize::try_from(xfer.rxCnt)
+        .unwrap_or(0)
+        .min(xfer.rxBuf.len());
+
+    BscTransfer {
+        status: status as u32,
+        rx: xfer.rxBuf[..count].iter().map(|&byte| byte as u8).collect(),
+    }
+}
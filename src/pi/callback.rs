@@ -0,0 +1,170 @@
+use std::collections;
+use std::ffi;
+use std::fmt;
+use std::sync;
+use std::sync::atomic;
+use std::time;
+
+use super::pigpiod_if2;
+use super::{Error, Gpio, GpioLevel, Init, Pi, PiConnection, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Rising = pigpiod_if2::RISING_EDGE as isize,
+    Falling = pigpiod_if2::FALLING_EDGE as isize,
+    Either = pigpiod_if2::EITHER_EDGE as isize,
+}
+
+type Closure = dyn FnMut(Gpio, GpioLevel, u32) + Send;
+
+static CLOSURES: Registry<Closure> = Registry::new();
+
+type Closures<T> = collections::BTreeMap<usize, sync::Arc<sync::Mutex<Box<T>>>>;
+
+// closures are handed to the C library by key instead of by pointer, its
+// dispatch thread may still be in one when it's cancelled and a running call
+// keeps its closure alive until it returns
+pub(super) struct Registry<T: ?Sized> {
+    closures: sync::Mutex<Closures<T>>,
+    next: atomic::AtomicUsize,
+}
+
+impl<T: ?Sized> Registry<T> {
+    pub(super) const fn new() -> Self {
+        Self {
+            closures: sync::Mutex::new(collections::BTreeMap::new()),
+            next: atomic::AtomicUsize::new(0),
+        }
+    }
+
+    pub(super) fn insert(&self, closure: Box<T>) -> usize {
+        let key = self.next.fetch_add(1, atomic::Ordering::Relaxed);
+        self.lock()
+            .insert(key, sync::Arc::new(sync::Mutex::new(closure)));
+        key
+    }
+
+    pub(super) fn remove(&self, key: usize) {
+        self.lock().remove(&key);
+    }
+
+    // runs the closure unless it was removed already
+    pub(super) fn call<F>(&self, key: usize, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        // not locked while it runs, it may register or cancel callbacks
+        let Some(closure) = self.lock().get(&key).cloned() else {
+            return;
+        };
+
+        let mut closure = closure.lock().unwrap_or_else(sync::PoisonError::into_inner);
+        f(&mut **closure);
+    }
+
+    fn lock(&self) -> sync::MutexGuard<'_, Closures<T>> {
+        self.closures
+            .lock()
+            .unwrap_or_else(sync::PoisonError::into_inner)
+    }
+}
+
+// cancelled when dropped
+pub struct Callback<C: PiConnection = Pi<Init>> {
+    // only kept so the connection outlives the callback
+    _pi: C,
+    id: ffi::c_uint,
+    key: usize,
+}
+
+impl<C: PiConnection> Callback<C> {
+    // `f` gets the pin, its new level and the tick of the change in microseconds
+    pub fn new<F>(pi: C, gpio: Gpio, edge: Edge, f: F) -> Result<Self>
+    where
+        F: FnMut(Gpio, GpioLevel, u32) + Send + 'static,
+    {
+        let key = CLOSURES.insert(Box::new(f));
+
+        let id = unsafe {
+            pigpiod_if2::callback_ex(
+                pi.pi().0 .0,
+                gpio.0,
+                edge as ffi::c_uint,
+                Some(trampoline),
+                key as *mut ffi::c_void,
+            )
+        };
+
+        if id.is_negative() {
+            CLOSURES.remove(key);
+            return Err(Error::new(id));
+        }
+
+        Ok(Self {
+            _pi: pi,
+            id: id as ffi::c_uint,
+            key,
+        })
+    }
+
+    pub fn cancel(self) {}
+}
+
+impl Pi<Init> {
+    pub fn callback<F>(&self, gpio: Gpio, edge: Edge, f: F) -> Result<Callback<&Pi<Init>>>
+    where
+        F: FnMut(Gpio, GpioLevel, u32) + Send + 'static,
+    {
+        Callback::new(self, gpio, edge, f)
+    }
+
+    // returns false if the timeout expired first
+    pub fn wait_for_edge(&self, gpio: Gpio, edge: Edge, timeout: time::Duration) -> Result<bool> {
+        let res = unsafe {
+            pigpiod_if2::wait_for_edge(
+                self.0 .0,
+                gpio.0,
+                edge as ffi::c_uint,
+                timeout.as_secs_f64(),
+            )
+        };
+
+        if res.is_negative() {
+            return Err(Error::new(res));
+        }
+
+        Ok(res == 1)
+    }
+}
+
+unsafe extern "C" fn trampoline(
+    _pi: ffi::c_int,
+    gpio: ffi::c_uint,
+    level: ffi::c_uint,
+    tick: u32,
+    userdata: *mut ffi::c_void,
+) {
+    let level = match level {
+        pigpiod_if2::PI_LOW => GpioLevel::Low,
+        pigpiod_if2::PI_HIGH => GpioLevel::High,
+        // watchdog timeout, nothing changed
+        _ => return,
+    };
+
+    CLOSURES.call(userdata as usize, |closure| {
+        closure(Gpio(gpio), level, tick)
+    });
+}
+
+impl<C: PiConnection> fmt::Debug for Callback<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Callback").field("id", &self.id).finish()
+    }
+}
+
+impl<C: PiConnection> Drop for Callback<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::callback_cancel(self.id) };
+        CLOSURES.remove(self.key);
+    }
+}
@@ -0,0 +1,117 @@
+use std::ffi;
+use std::ops;
+use std::time;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Gpio, GpioLevel, Init, Pi, Result};
+
+// the daemon's microsecond clock, it wraps around about every 71.6 minutes so
+// ticks are only compared by their distance and aren't ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tick(u32);
+
+impl Tick {
+    // e.g. the tick a callback got
+    pub fn new(tick: u32) -> Self {
+        Tick(tick)
+    }
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
+
+    // right as long as less than one wraparound passed between the two
+    pub fn duration_since(self, earlier: Tick) -> time::Duration {
+        time::Duration::from_micros(self.0.wrapping_sub(earlier.0).into())
+    }
+
+    pub fn wrapping_add(self, duration: time::Duration) -> Self {
+        // only the low 32 bits matter once it wraps
+        Tick(self.0.wrapping_add(duration.as_micros() as u32))
+    }
+}
+
+impl ops::Sub for Tick {
+    type Output = time::Duration;
+
+    fn sub(self, earlier: Tick) -> time::Duration {
+        self.duration_since(earlier)
+    }
+}
+
+// widens ticks to 64 bits that don't wrap, for timestamping long running
+// sampling, has to see a tick at least once per wraparound
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TickExtender {
+    last: Option<Tick>,
+    micros: u64,
+}
+
+impl TickExtender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // microseconds since the daemon's tick was last 0 before the first call
+    pub fn extend(&mut self, tick: Tick) -> u64 {
+        self.micros = match self.last {
+            Some(last) => self.micros + u64::from(tick.0.wrapping_sub(last.0)),
+            None => tick.0.into(),
+        };
+        self.last = Some(tick);
+        self.micros
+    }
+}
+
+// the host's wall clock, like the C library
+pub fn time_time() -> time::SystemTime {
+    let secs = unsafe { pigpiod_if2::time_time() };
+    time::UNIX_EPOCH + time::Duration::from_secs_f64(secs.max(0.))
+}
+
+// sleeps on the host, to wait for the Pi compare `Pi::current_tick`s
+pub fn time_sleep(duration: time::Duration) {
+    unsafe { pigpiod_if2::time_sleep(duration.as_secs_f64()) };
+}
+
+impl Pi<Init> {
+    // the C library can't tell errors from ticks, the socket client can
+    pub fn current_tick(&self) -> Result<Tick> {
+        #[cfg(feature = "socket")]
+        let tick = super::socket::command_u32(self.0 .0, super::protocol::PI_CMD_TICK)
+            .map_err(Error::new)?;
+        #[cfg(not(feature = "socket"))]
+        let tick = unsafe { pigpiod_if2::get_current_tick(self.0 .0) };
+
+        Ok(Tick(tick))
+    }
+
+    // drives a clock out of gpio 4, 5, 6, 20, 21, 32, 34, 42 or 43 (whichever
+    // are on the header), `frequency` in Hz is 4689-250M, 13184-375M on the
+    // BCM2711, 0 switches it off
+    pub fn hardware_clock(&self, gpio: Gpio, frequency: ffi::c_uint) -> Result<()> {
+        let err = unsafe { pigpiod_if2::hardware_clock(self.0 .0, gpio.0, frequency) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // sends a single pulse of 1-100 microseconds at `level`, after which the
+    // pin goes back to the opposite level
+    pub fn gpio_trigger(&self, gpio: Gpio, pulse: time::Duration, level: GpioLevel) -> Result<()> {
+        let pulse = ffi::c_uint::try_from(pulse.as_micros())
+            .map_err(|_| Error::Pi(ErrorCode::BadPulselen))?;
+
+        let err =
+            unsafe { pigpiod_if2::gpio_trigger(self.0 .0, gpio.0, pulse, level as ffi::c_uint) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+}
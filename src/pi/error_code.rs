@@ -0,0 +1,216 @@
+use std::ffi;
+use std::fmt;
+
+use super::pigpiod_if2;
+
+macro_rules! error_codes {
+    ($($variant:ident = $code:ident,)*) => {
+        // everything pigpiod and the client library report as negative return values
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ErrorCode {
+            $($variant = pigpiod_if2::$code as isize,)*
+        }
+
+        impl ErrorCode {
+            pub fn from_code(code: ffi::c_int) -> Option<Self> {
+                match code {
+                    $(pigpiod_if2::$code => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    InitFailed = PI_INIT_FAILED,
+    BadUserGpio = PI_BAD_USER_GPIO,
+    BadGpio = PI_BAD_GPIO,
+    BadMode = PI_BAD_MODE,
+    BadLevel = PI_BAD_LEVEL,
+    BadPud = PI_BAD_PUD,
+    BadPulsewidth = PI_BAD_PULSEWIDTH,
+    BadDutycycle = PI_BAD_DUTYCYCLE,
+    BadTimer = PI_BAD_TIMER,
+    BadMs = PI_BAD_MS,
+    BadTimetype = PI_BAD_TIMETYPE,
+    BadSeconds = PI_BAD_SECONDS,
+    BadMicros = PI_BAD_MICROS,
+    TimerFailed = PI_TIMER_FAILED,
+    BadWdogTimeout = PI_BAD_WDOG_TIMEOUT,
+    NoAlertFunc = PI_NO_ALERT_FUNC,
+    BadClkPeriph = PI_BAD_CLK_PERIPH,
+    BadClkSource = PI_BAD_CLK_SOURCE,
+    BadClkMicros = PI_BAD_CLK_MICROS,
+    BadBufMillis = PI_BAD_BUF_MILLIS,
+    BadDutyrange = PI_BAD_DUTYRANGE,
+    BadSignum = PI_BAD_SIGNUM,
+    BadPathname = PI_BAD_PATHNAME,
+    NoHandle = PI_NO_HANDLE,
+    BadHandle = PI_BAD_HANDLE,
+    BadIfFlags = PI_BAD_IF_FLAGS,
+    BadChannel = PI_BAD_CHANNEL,
+    BadSocketPort = PI_BAD_SOCKET_PORT,
+    BadFifoCommand = PI_BAD_FIFO_COMMAND,
+    BadSecoChannel = PI_BAD_SECO_CHANNEL,
+    NotInitialised = PI_NOT_INITIALISED,
+    Initialised = PI_INITIALISED,
+    BadWaveMode = PI_BAD_WAVE_MODE,
+    BadCfgInternal = PI_BAD_CFG_INTERNAL,
+    BadWaveBaud = PI_BAD_WAVE_BAUD,
+    TooManyPulses = PI_TOO_MANY_PULSES,
+    TooManyChars = PI_TOO_MANY_CHARS,
+    NotSerialGpio = PI_NOT_SERIAL_GPIO,
+    BadSerialStruc = PI_BAD_SERIAL_STRUC,
+    BadSerialBuf = PI_BAD_SERIAL_BUF,
+    NotPermitted = PI_NOT_PERMITTED,
+    SomePermitted = PI_SOME_PERMITTED,
+    BadWvscCommnd = PI_BAD_WVSC_COMMND,
+    BadWvsmCommnd = PI_BAD_WVSM_COMMND,
+    BadWvspCommnd = PI_BAD_WVSP_COMMND,
+    BadPulselen = PI_BAD_PULSELEN,
+    BadScript = PI_BAD_SCRIPT,
+    BadScriptId = PI_BAD_SCRIPT_ID,
+    BadSerOffset = PI_BAD_SER_OFFSET,
+    GpioInUse = PI_GPIO_IN_USE,
+    BadSerialCount = PI_BAD_SERIAL_COUNT,
+    BadParamNum = PI_BAD_PARAM_NUM,
+    DupTag = PI_DUP_TAG,
+    TooManyTags = PI_TOO_MANY_TAGS,
+    BadScriptCmd = PI_BAD_SCRIPT_CMD,
+    BadVarNum = PI_BAD_VAR_NUM,
+    NoScriptRoom = PI_NO_SCRIPT_ROOM,
+    NoMemory = PI_NO_MEMORY,
+    SockReadFailed = PI_SOCK_READ_FAILED,
+    SockWritFailed = PI_SOCK_WRIT_FAILED,
+    TooManyParam = PI_TOO_MANY_PARAM,
+    ScriptNotReady = PI_SCRIPT_NOT_READY,
+    BadTag = PI_BAD_TAG,
+    BadMicsDelay = PI_BAD_MICS_DELAY,
+    BadMilsDelay = PI_BAD_MILS_DELAY,
+    BadWaveId = PI_BAD_WAVE_ID,
+    TooManyCbs = PI_TOO_MANY_CBS,
+    TooManyOol = PI_TOO_MANY_OOL,
+    EmptyWaveform = PI_EMPTY_WAVEFORM,
+    NoWaveformId = PI_NO_WAVEFORM_ID,
+    I2cOpenFailed = PI_I2C_OPEN_FAILED,
+    SerOpenFailed = PI_SER_OPEN_FAILED,
+    SpiOpenFailed = PI_SPI_OPEN_FAILED,
+    BadI2cBus = PI_BAD_I2C_BUS,
+    BadI2cAddr = PI_BAD_I2C_ADDR,
+    BadSpiChannel = PI_BAD_SPI_CHANNEL,
+    BadFlags = PI_BAD_FLAGS,
+    BadSpiSpeed = PI_BAD_SPI_SPEED,
+    BadSerDevice = PI_BAD_SER_DEVICE,
+    BadSerSpeed = PI_BAD_SER_SPEED,
+    BadParam = PI_BAD_PARAM,
+    I2cWriteFailed = PI_I2C_WRITE_FAILED,
+    I2cReadFailed = PI_I2C_READ_FAILED,
+    BadSpiCount = PI_BAD_SPI_COUNT,
+    SerWriteFailed = PI_SER_WRITE_FAILED,
+    SerReadFailed = PI_SER_READ_FAILED,
+    SerReadNoData = PI_SER_READ_NO_DATA,
+    UnknownCommand = PI_UNKNOWN_COMMAND,
+    SpiXferFailed = PI_SPI_XFER_FAILED,
+    BadPointer = PI_BAD_POINTER,
+    NoAuxSpi = PI_NO_AUX_SPI,
+    NotPwmGpio = PI_NOT_PWM_GPIO,
+    NotServoGpio = PI_NOT_SERVO_GPIO,
+    NotHclkGpio = PI_NOT_HCLK_GPIO,
+    NotHpwmGpio = PI_NOT_HPWM_GPIO,
+    BadHpwmFreq = PI_BAD_HPWM_FREQ,
+    BadHpwmDuty = PI_BAD_HPWM_DUTY,
+    BadHclkFreq = PI_BAD_HCLK_FREQ,
+    BadHclkPass = PI_BAD_HCLK_PASS,
+    HpwmIllegal = PI_HPWM_ILLEGAL,
+    BadDatabits = PI_BAD_DATABITS,
+    BadStopbits = PI_BAD_STOPBITS,
+    MsgToobig = PI_MSG_TOOBIG,
+    BadMallocMode = PI_BAD_MALLOC_MODE,
+    TooManySegs = PI_TOO_MANY_SEGS,
+    BadI2cSeg = PI_BAD_I2C_SEG,
+    BadSmbusCmd = PI_BAD_SMBUS_CMD,
+    NotI2cGpio = PI_NOT_I2C_GPIO,
+    BadI2cWlen = PI_BAD_I2C_WLEN,
+    BadI2cRlen = PI_BAD_I2C_RLEN,
+    BadI2cCmd = PI_BAD_I2C_CMD,
+    BadI2cBaud = PI_BAD_I2C_BAUD,
+    ChainLoopCnt = PI_CHAIN_LOOP_CNT,
+    BadChainLoop = PI_BAD_CHAIN_LOOP,
+    ChainCounter = PI_CHAIN_COUNTER,
+    BadChainCmd = PI_BAD_CHAIN_CMD,
+    BadChainDelay = PI_BAD_CHAIN_DELAY,
+    ChainNesting = PI_CHAIN_NESTING,
+    ChainTooBig = PI_CHAIN_TOO_BIG,
+    Deprecated = PI_DEPRECATED,
+    BadSerInvert = PI_BAD_SER_INVERT,
+    BadEdge = PI_BAD_EDGE,
+    BadIsrInit = PI_BAD_ISR_INIT,
+    BadForever = PI_BAD_FOREVER,
+    BadFilter = PI_BAD_FILTER,
+    BadPad = PI_BAD_PAD,
+    BadStrength = PI_BAD_STRENGTH,
+    FilOpenFailed = PI_FIL_OPEN_FAILED,
+    BadFileMode = PI_BAD_FILE_MODE,
+    BadFileFlag = PI_BAD_FILE_FLAG,
+    BadFileRead = PI_BAD_FILE_READ,
+    BadFileWrite = PI_BAD_FILE_WRITE,
+    FileNotRopen = PI_FILE_NOT_ROPEN,
+    FileNotWopen = PI_FILE_NOT_WOPEN,
+    BadFileSeek = PI_BAD_FILE_SEEK,
+    NoFileMatch = PI_NO_FILE_MATCH,
+    NoFileAccess = PI_NO_FILE_ACCESS,
+    FileIsADir = PI_FILE_IS_A_DIR,
+    BadShellStatus = PI_BAD_SHELL_STATUS,
+    BadScriptName = PI_BAD_SCRIPT_NAME,
+    BadSpiBaud = PI_BAD_SPI_BAUD,
+    NotSpiGpio = PI_NOT_SPI_GPIO,
+    BadEventId = PI_BAD_EVENT_ID,
+    CmdInterrupted = PI_CMD_INTERRUPTED,
+    NotOnBcm2711 = PI_NOT_ON_BCM2711,
+    OnlyOnBcm2711 = PI_ONLY_ON_BCM2711,
+    // raised by the client library rather than the daemon
+    BadSend = pigif_bad_send,
+    BadRecv = pigif_bad_recv,
+    BadGetaddrinfo = pigif_bad_getaddrinfo,
+    BadConnect = pigif_bad_connect,
+    BadSocket = pigif_bad_socket,
+    BadNoib = pigif_bad_noib,
+    DuplicateCallback = pigif_duplicate_callback,
+    BadMalloc = pigif_bad_malloc,
+    BadCallback = pigif_bad_callback,
+    NotifyFailed = pigif_notify_failed,
+    CallbackNotFound = pigif_callback_not_found,
+    UnconnectedPi = pigif_unconnected_pi,
+    TooManyPis = pigif_too_many_pis,
+}
+
+impl ErrorCode {
+    pub fn code(self) -> ffi::c_int {
+        self as ffi::c_int
+    }
+
+    pub fn message(self) -> &'static ffi::CStr {
+        unsafe { ffi::CStr::from_ptr(pigpiod_if2::pigpio_error(self.code())) }
+    }
+
+    // the daemon can't be reached anymore, retrying needs a new connection
+    pub fn is_disconnected(self) -> bool {
+        matches!(
+            self,
+            Self::BadSend
+                | Self::BadRecv
+                | Self::BadGetaddrinfo
+                | Self::BadConnect
+                | Self::BadSocket
+                | Self::UnconnectedPi
+        )
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.message().to_bytes().escape_ascii().fmt(f)
+    }
+}
@@ -0,0 +1,175 @@
+use std::ffi;
+use std::fmt;
+use std::sync::mpsc;
+use std::time;
+
+use super::callback::Registry;
+use super::pigpiod_if2;
+use super::{Error, Init, Pi, PiConnection, Result, Tick};
+
+// events are raised by scripts, `event_trigger` or the BSC peripheral
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventId(ffi::c_uint);
+
+impl EventId {
+    // raised when the BSC peripheral received data
+    pub const BSC: Self = EventId(pigpiod_if2::PI_EVENT_BSC);
+
+    pub fn new(event: ffi::c_uint) -> Option<Self> {
+        if event > pigpiod_if2::PI_MAX_EVENT {
+            return None;
+        }
+
+        Some(EventId(event))
+    }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
+}
+
+type Closure = dyn FnMut(EventId, u32) + Send;
+
+static CLOSURES: Registry<Closure> = Registry::new();
+
+// cancelled when dropped
+pub struct EventCallback<C: PiConnection = Pi<Init>> {
+    // only kept so the connection outlives the callback
+    _pi: C,
+    id: ffi::c_uint,
+    key: usize,
+}
+
+// the ticks an event was raised at, in order, cancelled when dropped
+#[derive(Debug)]
+pub struct EventStream<C: PiConnection = Pi<Init>> {
+    _callback: EventCallback<C>,
+    rx: mpsc::Receiver<Tick>,
+}
+
+impl<C: PiConnection> EventStream<C> {
+    // buffers every time `event` is raised instead of handling it on the
+    // callback thread
+    pub fn new(pi: C, event: EventId) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+
+        let callback = EventCallback::new(pi, event, move |_, tick| {
+            // only fails while the stream is being dropped
+            let _ = tx.send(Tick::new(tick));
+        })?;
+
+        Ok(Self {
+            _callback: callback,
+            rx,
+        })
+    }
+
+    // `None` if the timeout expired first
+    pub fn recv_timeout(&self, timeout: time::Duration) -> Option<Tick> {
+        self.rx.recv_timeout(timeout).ok()
+    }
+
+    // doesn't wait
+    pub fn try_recv(&self) -> Option<Tick> {
+        self.rx.try_recv().ok()
+    }
+}
+
+// blocks until the event is raised again
+impl<C: PiConnection> Iterator for EventStream<C> {
+    type Item = Tick;
+
+    fn next(&mut self) -> Option<Tick> {
+        self.rx.recv().ok()
+    }
+}
+
+impl<C: PiConnection> EventCallback<C> {
+    // `f` gets the event and the tick it was raised at in microseconds
+    pub fn new<F>(pi: C, event: EventId, f: F) -> Result<Self>
+    where
+        F: FnMut(EventId, u32) + Send + 'static,
+    {
+        let key = CLOSURES.insert(Box::new(f));
+
+        let id = unsafe {
+            pigpiod_if2::event_callback_ex(
+                pi.pi().0 .0,
+                event.0,
+                Some(trampoline),
+                key as *mut ffi::c_void,
+            )
+        };
+
+        if id.is_negative() {
+            CLOSURES.remove(key);
+            return Err(Error::new(id));
+        }
+
+        Ok(Self {
+            _pi: pi,
+            id: id as ffi::c_uint,
+            key,
+        })
+    }
+
+    pub fn cancel(self) {}
+}
+
+impl Pi<Init> {
+    pub fn event_callback<F>(&self, event: EventId, f: F) -> Result<EventCallback<&Pi<Init>>>
+    where
+        F: FnMut(EventId, u32) + Send + 'static,
+    {
+        EventCallback::new(self, event, f)
+    }
+
+    pub fn event_stream(&self, event: EventId) -> Result<EventStream<&Pi<Init>>> {
+        EventStream::new(self, event)
+    }
+
+    // returns false if the timeout expired first
+    pub fn wait_for_event(&self, event: EventId, timeout: time::Duration) -> Result<bool> {
+        let res = unsafe { pigpiod_if2::wait_for_event(self.0 .0, event.0, timeout.as_secs_f64()) };
+
+        if res.is_negative() {
+            return Err(Error::new(res));
+        }
+
+        Ok(res == 1)
+    }
+
+    pub fn event_trigger(&self, event: EventId) -> Result<()> {
+        let err = unsafe { pigpiod_if2::event_trigger(self.0 .0, event.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+}
+
+unsafe extern "C" fn trampoline(
+    _pi: ffi::c_int,
+    event: ffi::c_uint,
+    tick: u32,
+    userdata: *mut ffi::c_void,
+) {
+    CLOSURES.call(userdata as usize, |closure| closure(EventId(event), tick));
+}
+
+impl<C: PiConnection> fmt::Debug for EventCallback<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventCallback")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl<C: PiConnection> Drop for EventCallback<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::event_callback_cancel(self.id) };
+        CLOSURES.remove(self.key);
+    }
+}
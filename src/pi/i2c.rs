@@ -0,0 +1,400 @@
+use std::ffi;
+
+use embedded_hal::i2c as hal;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Handle, Init, Pi, PiConnection, Result};
+
+// closed when dropped
+#[derive(Debug)]
+pub struct I2c<C: PiConnection = Pi<Init>> {
+    pi: C,
+    handle: Handle,
+}
+
+impl<C: PiConnection> I2c<C> {
+    pub fn open(pi: C, bus: ffi::c_uint, addr: u8) -> Result<Self> {
+        // no flags are defined yet
+        let handle = unsafe { pigpiod_if2::i2c_open(pi.pi().0 .0, bus, addr as ffi::c_uint, 0) };
+
+        if handle.is_negative() {
+            return Err(Error::new(handle));
+        }
+
+        Ok(Self {
+            pi,
+            handle: Handle(handle as ffi::c_uint),
+        })
+    }
+
+    pub fn close(self) {}
+
+    pub fn read_byte(&self) -> Result<u8> {
+        let byte = unsafe { pigpiod_if2::i2c_read_byte(self.pi.pi().0 .0, self.handle.0) };
+
+        if byte.is_negative() {
+            return Err(Error::new(byte));
+        }
+
+        Ok(byte as u8)
+    }
+
+    pub fn write_byte(&self, byte: u8) -> Result<()> {
+        let err = unsafe {
+            pigpiod_if2::i2c_write_byte(self.pi.pi().0 .0, self.handle.0, byte as ffi::c_uint)
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn read_byte_data(&self, reg: u8) -> Result<u8> {
+        let byte = unsafe {
+            pigpiod_if2::i2c_read_byte_data(self.pi.pi().0 .0, self.handle.0, reg as ffi::c_uint)
+        };
+
+        if byte.is_negative() {
+            return Err(Error::new(byte));
+        }
+
+        Ok(byte as u8)
+    }
+
+    pub fn write_byte_data(&self, reg: u8, byte: u8) -> Result<()> {
+        let err = unsafe {
+            pigpiod_if2::i2c_write_byte_data(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                byte as ffi::c_uint,
+            )
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // smbus words are little endian
+    pub fn read_word_data(&self, reg: u8) -> Result<u16> {
+        let word = unsafe {
+            pigpiod_if2::i2c_read_word_data(self.pi.pi().0 .0, self.handle.0, reg as ffi::c_uint)
+        };
+
+        if word.is_negative() {
+            return Err(Error::new(word));
+        }
+
+        Ok(word as u16)
+    }
+
+    pub fn write_word_data(&self, reg: u8, word: u16) -> Result<()> {
+        let err = unsafe {
+            pigpiod_if2::i2c_write_word_data(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                word as ffi::c_uint,
+            )
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // smbus block read, the device decides how many bytes (at most 32) it sends
+    pub fn read_block_data(&self, reg: u8, buf: &mut [u8; 32]) -> Result<usize> {
+        let pbuf = buf.as_mut_ptr().cast();
+
+        let count = unsafe {
+            pigpiod_if2::i2c_read_block_data(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                pbuf,
+            )
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn write_block_data(&self, reg: u8, bytes: &[u8]) -> Result<()> {
+        let pbuf = bytes.as_ptr().cast_mut().cast();
+        let buflen = bytes.len() as ffi::c_uint;
+
+        let err = unsafe {
+            pigpiod_if2::i2c_write_block_data(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                pbuf,
+                buflen,
+            )
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // plain i2c read starting at `reg`, without the smbus length byte
+    pub fn read_i2c_block_data(&self, reg: u8, buf: &mut [u8]) -> Result<usize> {
+        let pbuf = buf.as_mut_ptr().cast();
+        let buflen = buf.len() as ffi::c_uint;
+
+        let count = unsafe {
+            pigpiod_if2::i2c_read_i2c_block_data(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                pbuf,
+                buflen,
+            )
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn write_i2c_block_data(&self, reg: u8, bytes: &[u8]) -> Result<()> {
+        let pbuf = bytes.as_ptr().cast_mut().cast();
+        let buflen = bytes.len() as ffi::c_uint;
+
+        let err = unsafe {
+            pigpiod_if2::i2c_write_i2c_block_data(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                pbuf,
+                buflen,
+            )
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn read_device(&self, buf: &mut [u8]) -> Result<usize> {
+        let pbuf = buf.as_mut_ptr().cast();
+        let buflen = buf.len() as ffi::c_uint;
+
+        let count =
+            unsafe { pigpiod_if2::i2c_read_device(self.pi.pi().0 .0, self.handle.0, pbuf, buflen) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn write_device(&self, bytes: &[u8]) -> Result<()> {
+        let pbuf = bytes.as_ptr().cast_mut().cast();
+        let buflen = bytes.len() as ffi::c_uint;
+
+        let err = unsafe {
+            pigpiod_if2::i2c_write_device(self.pi.pi().0 .0, self.handle.0, pbuf, buflen)
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // smbus quick command, sends just the read/write bit
+    pub fn write_quick(&self, bit: bool) -> Result<()> {
+        let err = unsafe {
+            pigpiod_if2::i2c_write_quick(self.pi.pi().0 .0, self.handle.0, bit as ffi::c_uint)
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // writes `word` to `reg` and reads the device's answer
+    pub fn process_call(&self, reg: u8, word: u16) -> Result<u16> {
+        let word = unsafe {
+            pigpiod_if2::i2c_process_call(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                word as ffi::c_uint,
+            )
+        };
+
+        if word.is_negative() {
+            return Err(Error::new(word));
+        }
+
+        Ok(word as u16)
+    }
+
+    // writes the start of `buf` and replaces it with the device's answer,
+    // both at most 32 bytes, returns the length of the answer
+    pub fn block_process_call(&self, reg: u8, buf: &mut [u8; 32], len: usize) -> Result<usize> {
+        if len > buf.len() {
+            return Err(Error::Pi(ErrorCode::BadParam));
+        }
+
+        let pbuf = buf.as_mut_ptr().cast();
+
+        let count = unsafe {
+            pigpiod_if2::i2c_block_process_call(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                reg as ffi::c_uint,
+                pbuf,
+                len as ffi::c_uint,
+            )
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+}
+
+impl<C: PiConnection> Drop for I2c<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::i2c_close(self.pi.pi().0 .0, self.handle.0) };
+    }
+}
+
+impl hal::Error for Error {
+    fn kind(&self) -> hal::ErrorKind {
+        hal::ErrorKind::Other
+    }
+}
+
+impl<C: PiConnection> hal::ErrorType for I2c<C> {
+    type Error = Error;
+}
+
+// the handle's own address is only the default, every transaction names its target
+impl<C: PiConnection> hal::I2c for I2c<C> {
+    fn transaction(&mut self, address: u8, operations: &mut [hal::Operation<'_>]) -> Result<()> {
+        // all segments go out in one go, with repeated starts in between
+        let mut cmds = vec![
+            pigpiod_if2::PI_I2C_ADDR as u8,
+            address,
+            pigpiod_if2::PI_I2C_COMBINED_ON as u8,
+        ];
+        let read = encode_segments(&mut cmds, operations, None)?;
+        cmds.extend([
+            pigpiod_if2::PI_I2C_COMBINED_OFF as u8,
+            pigpiod_if2::PI_I2C_END as u8,
+        ]);
+
+        let mut out = vec![0; read];
+        let count = unsafe {
+            pigpiod_if2::i2c_zip(
+                self.pi.pi().0 .0,
+                self.handle.0,
+                cmds.as_mut_ptr().cast(),
+                cmds.len() as ffi::c_uint,
+                out.as_mut_ptr().cast(),
+                out.len() as ffi::c_uint,
+            )
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        // a short read would leave the end of the buffers stale
+        if count as usize != read {
+            return Err(Error::Pi(ErrorCode::I2cReadFailed));
+        }
+
+        decode_segments(&out, operations);
+        Ok(())
+    }
+}
+
+// appends a zip segment per run of reads or writes to `cmds`, each after
+// `start` if given, returns how many bytes will be read
+//
+// embedded-hal wants adjacent operations of the same kind merged, without a
+// restart or the address in between
+pub(super) fn encode_segments(
+    cmds: &mut Vec<u8>,
+    operations: &[hal::Operation<'_>],
+    start: Option<u8>,
+) -> Result<usize> {
+    const ESC: u8 = pigpiod_if2::PI_I2C_ESC as u8;
+
+    let mut read = 0;
+
+    for run in operations.chunk_by(|a, b| is_read(a) == is_read(b)) {
+        cmds.extend(start);
+
+        let len = run.iter().map(operation_len).sum::<usize>();
+
+        if is_read(&run[0]) {
+            let count = u16::try_from(len).map_err(|_| Error::Pi(ErrorCode::BadI2cRlen))?;
+            cmds.extend([ESC, pigpiod_if2::PI_I2C_READ as u8]);
+            cmds.extend(count.to_le_bytes());
+            read += len;
+        } else {
+            let count = u16::try_from(len).map_err(|_| Error::Pi(ErrorCode::BadI2cWlen))?;
+            cmds.extend([ESC, pigpiod_if2::PI_I2C_WRITE as u8]);
+            cmds.extend(count.to_le_bytes());
+            for operation in run {
+                if let hal::Operation::Write(bytes) = operation {
+                    cmds.extend_from_slice(bytes);
+                }
+            }
+        }
+    }
+
+    Ok(read)
+}
+
+fn is_read(operation: &hal::Operation<'_>) -> bool {
+    matches!(operation, hal::Operation::Read(_))
+}
+
+fn operation_len(operation: &hal::Operation<'_>) -> usize {
+    match operation {
+        hal::Operation::Read(buf) => buf.len(),
+        hal::Operation::Write(bytes) => bytes.len(),
+    }
+}
+
+// the merged reads arrive one after another, `out` has to hold all of them
+pub(super) fn decode_segments(mut out: &[u8], operations: &mut [hal::Operation<'_>]) {
+    for operation in operations {
+        if let hal::Operation::Read(buf) = operation {
+            let (head, tail) = out.split_at(buf.len());
+            buf.copy_from_slice(head);
+            out = tail;
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections;
+use std::ffi;
+use std::fs;
+use std::io;
+use std::net;
+use std::time;
+
+use super::pigpiod_if2;
+use super::protocol::{Header, Report, PI_CMD_NOIB};
+use super::{Edge, Error, Gpio, GpioLevel, GpioMask, Init, Pi, PiConnection, Result, Tick};
+
+// how long the reports of a window's last edges get to arrive after it
+const LATENCY: time::Duration = time::Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notification {
+    // `gpio` changed to `level` at `tick`
+    Level {
+        gpio: Gpio,
+        level: GpioLevel,
+        tick: Tick,
+    },
+    // the watchdog of `gpio` expired without a change
+    Timeout {
+        gpio: Gpio,
+        tick: Tick,
+    },
+}
+
+impl Notification {
+    pub fn gpio(&self) -> Gpio {
+        match *self {
+            Self::Level { gpio, .. } | Self::Timeout { gpio, .. } => gpio,
+        }
+    }
+
+    pub fn tick(&self) -> Tick {
+        match *self {
+            Self::Level { tick, .. } | Self::Timeout { tick, .. } => tick,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Source {
+    // the daemon's fifo, /dev/pigpio<handle>
+    Pipe(fs::File),
+    // a connection of its own, turned into a report stream
+    Socket(net::TcpStream),
+}
+
+impl Source {
+    // whether there's something to read before `deadline`
+    fn wait(&mut self, deadline: time::Instant) -> io::Result<bool> {
+        loop {
+            let timeout = deadline.saturating_duration_since(time::Instant::now());
+            if timeout.is_zero() {
+                return Ok(false);
+            }
+
+            match self {
+                #[cfg(unix)]
+                Self::Pipe(file) => {
+                    use std::os::fd::AsRawFd;
+
+                    let mut fd = libc::pollfd {
+                        fd: file.as_raw_fd(),
+                        events: libc::POLLIN,
+                        revents: 0,
+                    };
+                    let ms = timeout.as_micros().div_ceil(1000).min(i32::MAX as u128) as i32;
+
+                    match unsafe { libc::poll(&mut fd, 1, ms) } {
+                        0 => {}
+                        res if res > 0 => return Ok(true),
+                        _ => {
+                            let err = io::Error::last_os_error();
+                            if err.kind() != io::ErrorKind::Interrupted {
+                                return Err(err);
+                            }
+                        }
+                    }
+                }
+                // blocks like before
+                #[cfg(not(unix))]
+                Self::Pipe(_) => return Ok(true),
+                Self::Socket(stream) => {
+                    stream.set_read_timeout(Some(timeout))?;
+                    let ready = stream.peek(&mut [0]);
+                    stream.set_read_timeout(None)?;
+
+                    match ready {
+                        Ok(_) => return Ok(true),
+                        Err(err)
+                            if matches!(
+                                err.kind(),
+                                io::ErrorKind::WouldBlock
+                                    | io::ErrorKind::TimedOut
+                                    | io::ErrorKind::Interrupted
+                            ) => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl io::Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Pipe(file) => file.read(buf),
+            Self::Socket(stream) => stream.read(buf),
+        }
+    }
+}
+
+// every level change of the watched gpios, timestamped and in order, unlike
+// callbacks nothing gets lost as long as it's read fast enough for the
+// daemon's buffer, closed when dropped
+#[derive(Debug)]
+pub struct Notify<C: PiConnection = Pi<Init>> {
+    pi: C,
+    handle: ffi::c_uint,
+    source: Source,
+    mask: GpioMask,
+    // levels as of the last report, changes are derived from it
+    last: u32,
+    pending: collections::VecDeque<Notification>,
+}
+
+impl<C: PiConnection> Notify<C> {
+    // reads the daemon's fifo, so this only works on the Pi itself
+    pub fn open(pi: C) -> Result<Self> {
+        let handle = unsafe { pigpiod_if2::notify_open(pi.pi().0 .0) };
+
+        if handle.is_negative() {
+            return Err(Error::new(handle));
+        }
+
+        let pipe = match fs::File::open(format!("/dev/pigpio{handle}")) {
+            Ok(pipe) => pipe,
+            Err(err) => {
+                unsafe { pigpiod_if2::notify_close(pi.pi().0 .0, handle as ffi::c_uint) };
+                return Err(err.into());
+            }
+        };
+
+        Ok(Self::new(pi, handle as ffi::c_uint, Source::Pipe(pipe)))
+    }
+
+    // reports come over a connection of their own to `addr`, which has to be
+    // the daemon `pi` is connected to, works from anywhere
+    pub fn open_in_band<A>(pi: C, addr: A) -> Result<Self>
+    where
+        A: net::ToSocketAddrs,
+    {
+        let mut stream = net::TcpStream::connect(addr)?;
+
+        Header::new(PI_CMD_NOIB, 0, 0, 0).write_to(&mut stream, &[])?;
+        let handle = Header::read_from(&mut stream)?.res();
+
+        if handle.is_negative() {
+            return Err(Error::new(handle));
+        }
+
+        Ok(Self::new(pi, handle as ffi::c_uint, Source::Socket(stream)))
+    }
+
+    fn new(pi: C, handle: ffi::c_uint, source: Source) -> Self {
+        Self {
+            pi,
+            handle,
+            source,
+            mask: GpioMask::EMPTY,
+            last: 0,
+            pending: collections::VecDeque::new(),
+        }
+    }
+
+    pub fn handle(&self) -> ffi::c_uint {
+        self.handle
+    }
+
+    // starts (or changes) reporting the gpios in `mask`
+    pub fn begin(&mut self, mask: GpioMask) -> Result<()> {
+        // pins that weren't watched didn't get reported, so the last levels may be stale
+        self.last = self.pi.pi().read_bank_1()?.bits();
+
+        let err = unsafe { pigpiod_if2::notify_begin(self.pi.pi().0 .0, self.handle, mask.bits()) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        self.mask = mask;
+        Ok(())
+    }
+
+    // stops reporting until `begin` is called again
+    pub fn pause(&mut self) -> Result<()> {
+        let err = unsafe { pigpiod_if2::notify_pause(self.pi.pi().0 .0, self.handle) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        self.mask = GpioMask::EMPTY;
+        Ok(())
+    }
+
+    pub fn close(self) {}
+
+    // feeds `counter` until `window` has passed by the daemon's clock, or by
+    // this one's if the pin stays quiet, fails if the notifications end first
+    pub fn count_pulses(
+        &mut self,
+        counter: &mut PulseCounter,
+        window: time::Duration,
+    ) -> Result<()> {
+        let start = self.pi.pi().current_tick()?;
+        let deadline = time::Instant::now() + window + LATENCY;
+
+        while let Some(notification) = self.next_before(deadline)? {
+            let since = notification.tick().get().wrapping_sub(start.get());
+
+            // still queued from before the call
+            if (since as i32).is_negative() {
+                continue;
+            }
+
+            // the first one past the window belongs to whatever comes next
+            if notification.tick() - start >= window {
+                self.pending.push_front(notification);
+                break;
+            }
+
+            counter.feed(&notification);
+        }
+
+        Ok(())
+    }
+
+    // `None` once `deadline` passed without one
+    fn next_before(&mut self, deadline: time::Instant) -> Result<Option<Notification>> {
+        while self.pending.is_empty() {
+            if !self.source.wait(deadline)? {
+                return Ok(None);
+            }
+
+            match self.read_report() {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Err(Error::other("notifications ended before the window"))
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(self.pending.pop_front())
+    }
+
+    fn read_report(&mut self) -> io::Result<()> {
+        let report = Report::read_from(&mut self.source)?;
+        let flags = u32::from(report.flags);
+        let tick = Tick::new(report.tick);
+
+        // events aren't asked for, keepalives carry nothing
+        if flags & (pigpiod_if2::PI_NTFY_FLAGS_EVENT | pigpiod_if2::PI_NTFY_FLAGS_ALIVE) != 0 {
+            return Ok(());
+        }
+
+        if flags & pigpiod_if2::PI_NTFY_FLAGS_WDOG != 0 {
+            let gpio = flags & pigpiod_if2::PI_MAX_USER_GPIO;
+            self.pending.push_back(Notification::Timeout {
+                gpio: Gpio(gpio),
+                tick,
+            });
+            return Ok(());
+        }
+
+        let changed = (report.level ^ self.last) & self.mask.bits();
+        self.last = report.level;
+
+        let changes = (0..=pigpiod_if2::PI_MAX_USER_GPIO)
+            .filter(|gpio| changed >> gpio & 1 == 1)
+            .map(|gpio| Notification::Level {
+                gpio: Gpio(gpio),
+                level: GpioLevel::from(report.level >> gpio & 1 == 1),
+                tick,
+            });
+        self.pending.extend(changes);
+
+        Ok(())
+    }
+}
+
+// blocks until the next change, ends when the daemon closes the notification
+impl<C: PiConnection> Iterator for Notify<C> {
+    type Item = Result<Notification>;
+
+    fn next(&mut self) -> Option<Result<Notification>> {
+        while self.pending.is_empty() {
+            match self.read_report() {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return None,
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+impl<C: PiConnection> Drop for Notify<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::notify_close(self.pi.pi().0 .0, self.handle) };
+    }
+}
+
+// counts the edges of one gpio, e.g. of an anemometer or a flow meter
+#[derive(Debug, Clone, Copy)]
+pub struct PulseCounter {
+    gpio: Gpio,
+    edge: Edge,
+    count: u64,
+    first: Option<Tick>,
+    last: Option<Tick>,
+}
+
+impl PulseCounter {
+    pub fn new(gpio: Gpio, edge: Edge) -> Self {
+        Self {
+            gpio,
+            edge,
+            count: 0,
+            first: None,
+            last: None,
+        }
+    }
+
+    // anything but a matching edge is ignored
+    pub fn feed(&mut self, notification: &Notification) {
+        let Notification::Level { gpio, level, tick } = *notification else {
+            return;
+        };
+
+        let wanted = match self.edge {
+            Edge::Rising => level == GpioLevel::High,
+            Edge::Falling => level == GpioLevel::Low,
+            Edge::Either => true,
+        };
+
+        if gpio == self.gpio && wanted {
+            self.count += 1;
+            self.first.get_or_insert(tick);
+            self.last = Some(tick);
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    // edges per second, measured from the first to the last edge counted so
+    // it doesn't depend on when the window started, `None` below two edges
+    pub fn frequency(&self) -> Option<f64> {
+        let (first, last) = (self.first?, self.last?);
+        let span = (last - first).as_secs_f64();
+
+        if self.count < 2 || span == 0. {
+            return None;
+        }
+
+        Some((self.count - 1) as f64 / span)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.gpio, self.edge);
+    }
+}
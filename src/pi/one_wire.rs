@@ -0,0 +1,382 @@
+use std::cell;
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::str;
+use std::thread;
+use std::time;
+
+use super::{Error, ErrorCode, Gpio, Init, Pi, PiConnection, Result, Script, ScriptStatus};
+
+// the slots are a few microseconds long, far too short for round trips and
+// waves can't release the line, so they're timed by a script on the daemon
+// that pulls the line low by switching the pin to output with its level
+// latched low and releases it by switching back to input
+//
+// p0 is the gpio, p1 the operation, p2 the data and p3 the number of bits:
+// 0 resets the bus and sets p2 if a device answered
+// 1 writes the low p3 bits of p2, lsb first
+// 2 reads p3 bits into p2, lsb first
+// 3 reads a bit and its complement, then writes the bit if they differ and
+//   p2 otherwise, p2 = bit | complement << 1 | written << 2
+const SCRIPT: &str = "\
+lda p1 jz 10 sub 1 jz 20 sub 1 jz 30 sub 1 jz 40 halt \
+tag 10 w p0 0 mics 480 m p0 r mics 70 r p0 xor 1 sta p2 mics 410 halt \
+tag 20 ld v0 p3 \
+tag 21 lda p2 and 1 sta v3 call 50 rr p2 1 dcr v0 jnz 21 halt \
+tag 30 ld v0 p3 ld v1 0 ld v2 1 \
+tag 31 call 60 lda v3 jz 32 lda v1 or v2 sta v1 \
+tag 32 rl v2 1 dcr v0 jnz 31 lda v1 sta p2 halt \
+tag 40 call 60 lda v3 sta v1 call 60 lda v3 sta v2 xor v1 jz 41 lda v1 sta v3 jmp 42 \
+tag 41 lda p2 sta v3 \
+tag 42 call 50 lda v3 rla 1 or v2 rla 1 or v1 sta p2 halt \
+tag 50 lda v3 jz 51 m p0 w mics 6 m p0 r mics 64 ret \
+tag 51 m p0 w mics 60 m p0 r mics 10 ret \
+tag 60 m p0 w mics 2 m p0 r mics 10 r p0 sta v3 mics 50 ret";
+
+const RESET: u32 = 0;
+const WRITE: u32 = 1;
+const READ: u32 = 2;
+const TRIPLET: u32 = 3;
+
+const SEARCH_ROM: u8 = 0xf0;
+const READ_ROM: u8 = 0x33;
+const MATCH_ROM: u8 = 0x55;
+const SKIP_ROM: u8 = 0xcc;
+const CONVERT_T: u8 = 0x44;
+const READ_SCRATCHPAD: u8 = 0xbe;
+
+// the longest conversion, at 12 bits
+const CONVERSION: time::Duration = time::Duration::from_millis(750);
+
+// the longest operation, a reset, takes about 1ms on the bus, the rest is
+// left for the round trips
+const OPERATION: time::Duration = time::Duration::from_millis(20);
+const POLL: time::Duration = time::Duration::from_micros(100);
+
+// the 64 bit id of a device, family code in the low byte and crc in the high
+// byte, as sent on the bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rom(u64);
+
+impl Rom {
+    // `None` if the crc doesn't match
+    pub fn new(rom: u64) -> Option<Self> {
+        let bytes = rom.to_le_bytes();
+
+        if crc8(&bytes[..7]) != bytes[7] {
+            return None;
+        }
+
+        Some(Rom(rom))
+    }
+
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    // e.g. 0x10 for the DS18S20 and 0x28 for the DS18B20
+    pub fn family(self) -> u8 {
+        self.0 as u8
+    }
+
+    pub fn serial(self) -> u64 {
+        self.0 >> 8 & 0xffff_ffff_ffff
+    }
+}
+
+// like the kernel names it under /sys/bus/w1/devices, e.g. 10-00080253aa82
+impl fmt::Display for Rom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02x}-{:012x}", self.family(), self.serial())
+    }
+}
+
+impl str::FromStr for Rom {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (family, serial) = s.split_once('-').ok_or(Error::Pi(ErrorCode::BadParam))?;
+        let family = u8::from_str_radix(family, 16)?;
+        let serial = u64::from_str_radix(serial, 16)?;
+
+        if serial > 0xffff_ffff_ffff {
+            return Err(Error::Pi(ErrorCode::BadParam));
+        }
+
+        // the kernel leaves out the crc, it follows from the rest
+        let rom = serial << 8 | u64::from(family);
+        let crc = crc8(&rom.to_le_bytes()[..7]);
+        Ok(Rom(rom | u64::from(crc) << 56))
+    }
+}
+
+// something went wrong on the bus itself, trying again may well work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusError {
+    // no device answered the reset
+    NoPresence,
+    CrcMismatch,
+    // passed the crc but can't be right, e.g. all zeros from a line held low
+    BadScratchpad,
+}
+
+impl fmt::Display for BusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoPresence => f.write_str("no 1-wire device present"),
+            Self::CrcMismatch => f.write_str("1-wire crc mismatch"),
+            Self::BadScratchpad => f.write_str("bad 1-wire scratchpad"),
+        }
+    }
+}
+
+impl error::Error for BusError {}
+
+impl From<BusError> for Error {
+    fn from(error: BusError) -> Self {
+        Self::other(error)
+    }
+}
+
+// the Dallas/Maxim crc, over everything but the crc itself
+pub fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 1 == 1 {
+                crc >> 1 ^ 0x8c
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+// in °C from the scratchpad of a DS18S20, DS1822 or DS18B20
+pub fn temperature(family: u8, scratchpad: [u8; 9]) -> Result<f32> {
+    if crc8(&scratchpad[..8]) != scratchpad[8] {
+        return Err(BusError::CrcMismatch.into());
+    }
+
+    // the crc of all zeros is zero too
+    if scratchpad == [0; 9] {
+        return Err(BusError::BadScratchpad.into());
+    }
+
+    let raw = i16::from_le_bytes([scratchpad[0], scratchpad[1]]);
+
+    let temperature = match family {
+        // half degrees, refined by the counter like its datasheet says
+        0x10 => {
+            let count_remain = f32::from(scratchpad[6]);
+            let count_per_c = f32::from(scratchpad[7]);
+            if count_per_c == 0. {
+                return Err(BusError::BadScratchpad.into());
+            }
+            f32::from(raw >> 1) - 0.25 + (count_per_c - count_remain) / count_per_c
+        }
+        _ => f32::from(raw) / 16.,
+    };
+
+    Ok(temperature)
+}
+
+// 1-Wire on any gpio, without the kernel's w1-gpio overlay, the line needs
+// its pull-up (4.7k) and the devices their own supply, parasite power isn't
+// supported
+#[derive(Debug)]
+pub struct OneWire<C: PiConnection = Pi<Init>> {
+    script: cell::RefCell<Script<C>>,
+    gpio: Gpio,
+}
+
+impl<C: PiConnection> OneWire<C> {
+    pub fn open(pi: C, gpio: Gpio) -> Result<Self> {
+        let script = Script::store(pi, SCRIPT)?;
+        checked(&script)?;
+
+        Ok(Self {
+            script: cell::RefCell::new(script),
+            gpio,
+        })
+    }
+
+    pub fn close(self) {}
+
+    pub fn gpio(&self) -> Gpio {
+        self.gpio
+    }
+
+    // returns false if no device answered
+    pub fn reset(&self) -> Result<bool> {
+        Ok(self.run(RESET, 0, 0)? == 1)
+    }
+
+    pub fn write_bit(&self, bit: bool) -> Result<()> {
+        self.run(WRITE, bit.into(), 1)?;
+        Ok(())
+    }
+
+    pub fn read_bit(&self) -> Result<bool> {
+        Ok(self.run(READ, 0, 1)? == 1)
+    }
+
+    pub fn write_byte(&self, byte: u8) -> Result<()> {
+        self.run(WRITE, byte.into(), 8)?;
+        Ok(())
+    }
+
+    pub fn read_byte(&self) -> Result<u8> {
+        Ok(self.run(READ, 0, 8)? as u8)
+    }
+
+    pub fn write(&self, bytes: &[u8]) -> Result<()> {
+        bytes.iter().try_for_each(|&byte| self.write_byte(byte))
+    }
+
+    pub fn read(&self, buf: &mut [u8]) -> Result<()> {
+        for byte in buf {
+            *byte = self.read_byte()?;
+        }
+
+        Ok(())
+    }
+
+    // resets the bus and addresses `rom`, or every device if there is none
+    pub fn select(&self, rom: Option<Rom>) -> Result<()> {
+        if !self.reset()? {
+            return Err(BusError::NoPresence.into());
+        }
+
+        match rom {
+            Some(rom) => {
+                self.write_byte(MATCH_ROM)?;
+                self.write(&rom.0.to_le_bytes())
+            }
+            None => self.write_byte(SKIP_ROM),
+        }
+    }
+
+    // only works with a single device on the bus
+    pub fn read_rom(&self) -> Result<Rom> {
+        if !self.reset()? {
+            return Err(BusError::NoPresence.into());
+        }
+
+        self.write_byte(READ_ROM)?;
+
+        let mut bytes = [0; 8];
+        self.read(&mut bytes)?;
+
+        Ok(Rom::new(u64::from_le_bytes(bytes)).ok_or(BusError::CrcMismatch)?)
+    }
+
+    // every device on the bus, by Maxim's search algorithm
+    pub fn search(&self) -> Result<Vec<Rom>> {
+        let mut roms = Vec::new();
+        let mut rom = 0u64;
+        // the highest bit at which both ways have yet to be taken, 1-based
+        let mut last_discrepancy = 0;
+
+        loop {
+            if !self.reset()? {
+                return Ok(roms);
+            }
+
+            self.write_byte(SEARCH_ROM)?;
+
+            let mut last_zero = 0;
+
+            for bit in 1..=64 {
+                let preferred = match bit.cmp(&last_discrepancy) {
+                    cmp::Ordering::Less => rom >> (bit - 1) & 1 == 1,
+                    cmp::Ordering::Equal => true,
+                    cmp::Ordering::Greater => false,
+                };
+
+                let triplet = self.run(TRIPLET, preferred.into(), 0)?;
+                let (id, complement, taken) = (triplet & 1, triplet >> 1 & 1, triplet >> 2 & 1);
+
+                // nobody answered, e.g. a device left mid search
+                if id == 1 && complement == 1 {
+                    return Ok(roms);
+                }
+
+                if id == 0 && complement == 0 && taken == 0 {
+                    last_zero = bit;
+                }
+
+                rom = rom & !(1 << (bit - 1)) | u64::from(taken) << (bit - 1);
+            }
+
+            roms.push(Rom::new(rom).ok_or(BusError::CrcMismatch)?);
+
+            last_discrepancy = last_zero;
+            if last_discrepancy == 0 {
+                return Ok(roms);
+            }
+        }
+    }
+
+    // in °C from a DS18S20, DS1822 or DS18B20, waits for the conversion
+    pub fn read_temperature(&self, rom: Rom) -> Result<f32> {
+        self.select(Some(rom))?;
+        self.write_byte(CONVERT_T)?;
+        thread::sleep(CONVERSION);
+
+        self.select(Some(rom))?;
+        self.write_byte(READ_SCRATCHPAD)?;
+
+        let mut scratchpad = [0; 9];
+        self.read(&mut scratchpad)?;
+
+        temperature(rom.family(), scratchpad)
+    }
+
+    // returns p2 once the script halted, stores the script again if the
+    // daemon restarted in between and lost it
+    fn run(&self, op: u32, data: u32, bits: u32) -> Result<u32> {
+        match self.try_run(op, data, bits) {
+            Err(Error::Pi(ErrorCode::BadScriptId)) => {
+                let mut script = self.script.borrow_mut();
+                script.restore(SCRIPT)?;
+                checked(&script)?;
+                drop(script);
+
+                self.try_run(op, data, bits)
+            }
+            res => res,
+        }
+    }
+
+    fn try_run(&self, op: u32, data: u32, bits: u32) -> Result<u32> {
+        let script = self.script.borrow();
+        script.run(&[self.gpio.0, op, data, bits])?;
+        let deadline = time::Instant::now() + OPERATION;
+
+        loop {
+            match script.status()? {
+                (ScriptStatus::Halted, params) => return Ok(params[2]),
+                (ScriptStatus::Running | ScriptStatus::Waiting, _) => {
+                    if time::Instant::now() >= deadline {
+                        let _ = script.stop();
+                        return Err(Error::other("1-wire script timed out"));
+                    }
+                    thread::sleep(POLL);
+                }
+                _ => return Err(Error::Pi(ErrorCode::BadScript)),
+            }
+        }
+    }
+}
+
+// waits until the daemon is done checking the script
+fn checked<C: PiConnection>(script: &Script<C>) -> Result<()> {
+    loop {
+        match script.status()?.0 {
+            ScriptStatus::Initing => thread::sleep(time::Duration::from_millis(1)),
+            ScriptStatus::Halted => return Ok(()),
+            _ => return Err(Error::Pi(ErrorCode::BadScript)),
+        }
+    }
+}
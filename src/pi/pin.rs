@@ -0,0 +1,168 @@
+use std::thread;
+use std::time;
+
+use embedded_hal::delay;
+use embedded_hal::digital as hal;
+
+use crate::backend::GpioBackend;
+
+use super::{Error, Gpio, GpioLevel, GpioMode, Init, Pi, Result};
+
+// a gpio driven as an output, released to input when dropped
+#[derive(Debug)]
+pub struct Output<B: GpioBackend = Pi<Init>> {
+    pi: B,
+    gpio: Gpio,
+}
+
+impl<B: GpioBackend> Output<B> {
+    pub fn new(pi: B, gpio: Gpio, level: GpioLevel) -> Result<Self> {
+        pi.set_mode(gpio, GpioMode::Output)?;
+        pi.gpio_write(gpio, level)?;
+        Ok(Self { pi, gpio })
+    }
+
+    pub fn gpio(&self) -> Gpio {
+        self.gpio
+    }
+
+    // for whatever needs more of the backend than the pin, like waves
+    pub(crate) fn pi(&self) -> &B {
+        &self.pi
+    }
+
+    pub fn write(&self, level: GpioLevel) -> Result<()> {
+        self.pi.gpio_write(self.gpio, level)
+    }
+
+    // the level the pin is driven to
+    pub fn read(&self) -> Result<GpioLevel> {
+        self.pi.gpio_read(self.gpio)
+    }
+}
+
+impl<B: GpioBackend> Drop for Output<B> {
+    fn drop(&mut self) {
+        let _ = self.pi.set_mode(self.gpio, GpioMode::Input);
+    }
+}
+
+#[derive(Debug)]
+pub struct Input<B: GpioBackend = Pi<Init>> {
+    pi: B,
+    gpio: Gpio,
+}
+
+impl<B: GpioBackend> Input<B> {
+    pub fn new(pi: B, gpio: Gpio) -> Result<Self> {
+        pi.set_mode(gpio, GpioMode::Input)?;
+        Ok(Self { pi, gpio })
+    }
+
+    pub fn gpio(&self) -> Gpio {
+        self.gpio
+    }
+
+    pub fn read(&self) -> Result<GpioLevel> {
+        self.pi.gpio_read(self.gpio)
+    }
+}
+
+impl hal::Error for Error {
+    fn kind(&self) -> hal::ErrorKind {
+        hal::ErrorKind::Other
+    }
+}
+
+impl<B: GpioBackend> hal::ErrorType for Output<B> {
+    type Error = Error;
+}
+
+impl<B: GpioBackend> hal::OutputPin for Output<B> {
+    fn set_low(&mut self) -> Result<()> {
+        self.write(GpioLevel::Low)
+    }
+
+    fn set_high(&mut self) -> Result<()> {
+        self.write(GpioLevel::High)
+    }
+}
+
+impl<B: GpioBackend> hal::StatefulOutputPin for Output<B> {
+    fn is_set_high(&mut self) -> Result<bool> {
+        Ok(self.read()? == GpioLevel::High)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool> {
+        Ok(self.read()? == GpioLevel::Low)
+    }
+}
+
+impl<B: GpioBackend> hal::ErrorType for Input<B> {
+    type Error = Error;
+}
+
+impl<B: GpioBackend> hal::InputPin for Input<B> {
+    fn is_high(&mut self) -> Result<bool> {
+        Ok(self.read()? == GpioLevel::High)
+    }
+
+    fn is_low(&mut self) -> Result<bool> {
+        Ok(self.read()? == GpioLevel::Low)
+    }
+}
+
+// the local sleep covers most of a delay, these only make up for the
+// difference between the clocks
+const MAX_POLLS: usize = 1000;
+
+// delays measured with the daemon's tick, so they hold on the Pi's side
+// no matter how long the round trips take
+#[derive(Debug)]
+pub struct Delay<B: GpioBackend = Pi<Init>> {
+    pi: B,
+}
+
+impl<B: GpioBackend> Delay<B> {
+    pub fn new(pi: B) -> Self {
+        Self { pi }
+    }
+
+    fn wait(&self, us: u64) {
+        let start = self.pi.current_tick();
+        thread::sleep(time::Duration::from_micros(us));
+
+        // delays can't fail, without a tick the local clock has to do
+        let Ok(start) = start else {
+            return;
+        };
+
+        // the tick wraps after about 72 minutes
+        if us > u64::from(u32::MAX / 2) {
+            return;
+        }
+
+        // a daemon whose tick doesn't move on, or that went away while a
+        // reconnecting backend waits for it, mustn't hold the caller forever
+        for _ in 0..MAX_POLLS {
+            match self.pi.current_tick() {
+                Ok(now) if u64::from(now.wrapping_sub(start)) < us => {}
+                _ => break,
+            }
+        }
+    }
+}
+
+impl<B: GpioBackend> delay::DelayNs for Delay<B> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.wait(u64::from(ns.div_ceil(1000)));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.wait(u64::from(us));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.wait(u64::from(ms) * 1000);
+    }
+}
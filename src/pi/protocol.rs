@@ -0,0 +1,462 @@
+// wire format and constants of the pigpiod socket interface, see
+// https://abyz.me.uk/rpi/pigpio/sif.html
+//
+// every request and response starts with four little endian words: the
+// command, two parameters and either the length of the extension that
+// follows (request) or the result (response)
+
+// full command and error tables, not everything is used by every client
+#![allow(dead_code)]
+#![allow(non_upper_case_globals)]
+
+use std::io;
+
+pub const PI_CMD_MODES: u32 = 0;
+pub const PI_CMD_MODEG: u32 = 1;
+pub const PI_CMD_PUD: u32 = 2;
+pub const PI_CMD_READ: u32 = 3;
+pub const PI_CMD_WRITE: u32 = 4;
+pub const PI_CMD_PWM: u32 = 5;
+pub const PI_CMD_PRS: u32 = 6;
+pub const PI_CMD_PFS: u32 = 7;
+pub const PI_CMD_SERVO: u32 = 8;
+pub const PI_CMD_WDOG: u32 = 9;
+pub const PI_CMD_BR1: u32 = 10;
+pub const PI_CMD_BR2: u32 = 11;
+pub const PI_CMD_BC1: u32 = 12;
+pub const PI_CMD_BC2: u32 = 13;
+pub const PI_CMD_BS1: u32 = 14;
+pub const PI_CMD_BS2: u32 = 15;
+pub const PI_CMD_TICK: u32 = 16;
+pub const PI_CMD_HWVER: u32 = 17;
+pub const PI_CMD_NO: u32 = 18;
+pub const PI_CMD_NB: u32 = 19;
+pub const PI_CMD_NP: u32 = 20;
+pub const PI_CMD_NC: u32 = 21;
+pub const PI_CMD_PRG: u32 = 22;
+pub const PI_CMD_PFG: u32 = 23;
+pub const PI_CMD_PRRG: u32 = 24;
+pub const PI_CMD_HELP: u32 = 25;
+pub const PI_CMD_PIGPV: u32 = 26;
+pub const PI_CMD_WVCLR: u32 = 27;
+pub const PI_CMD_WVAG: u32 = 28;
+pub const PI_CMD_WVAS: u32 = 29;
+pub const PI_CMD_WVGO: u32 = 30;
+pub const PI_CMD_WVGOR: u32 = 31;
+pub const PI_CMD_WVBSY: u32 = 32;
+pub const PI_CMD_WVHLT: u32 = 33;
+pub const PI_CMD_WVSM: u32 = 34;
+pub const PI_CMD_WVSP: u32 = 35;
+pub const PI_CMD_WVSC: u32 = 36;
+pub const PI_CMD_TRIG: u32 = 37;
+pub const PI_CMD_PROC: u32 = 38;
+pub const PI_CMD_PROCD: u32 = 39;
+pub const PI_CMD_PROCR: u32 = 40;
+pub const PI_CMD_PROCS: u32 = 41;
+pub const PI_CMD_SLRO: u32 = 42;
+pub const PI_CMD_SLR: u32 = 43;
+pub const PI_CMD_SLRC: u32 = 44;
+pub const PI_CMD_PROCP: u32 = 45;
+pub const PI_CMD_MICS: u32 = 46;
+pub const PI_CMD_MILS: u32 = 47;
+pub const PI_CMD_PARSE: u32 = 48;
+pub const PI_CMD_WVCRE: u32 = 49;
+pub const PI_CMD_WVDEL: u32 = 50;
+pub const PI_CMD_WVTX: u32 = 51;
+pub const PI_CMD_WVTXR: u32 = 52;
+pub const PI_CMD_WVNEW: u32 = 53;
+pub const PI_CMD_I2CO: u32 = 54;
+pub const PI_CMD_I2CC: u32 = 55;
+pub const PI_CMD_I2CRD: u32 = 56;
+pub const PI_CMD_I2CWD: u32 = 57;
+pub const PI_CMD_I2CWQ: u32 = 58;
+pub const PI_CMD_I2CRS: u32 = 59;
+pub const PI_CMD_I2CWS: u32 = 60;
+pub const PI_CMD_I2CRB: u32 = 61;
+pub const PI_CMD_I2CWB: u32 = 62;
+pub const PI_CMD_I2CRW: u32 = 63;
+pub const PI_CMD_I2CWW: u32 = 64;
+pub const PI_CMD_I2CRK: u32 = 65;
+pub const PI_CMD_I2CWK: u32 = 66;
+pub const PI_CMD_I2CRI: u32 = 67;
+pub const PI_CMD_I2CWI: u32 = 68;
+pub const PI_CMD_I2CPC: u32 = 69;
+pub const PI_CMD_I2CPK: u32 = 70;
+pub const PI_CMD_SPIO: u32 = 71;
+pub const PI_CMD_SPIC: u32 = 72;
+pub const PI_CMD_SPIR: u32 = 73;
+pub const PI_CMD_SPIW: u32 = 74;
+pub const PI_CMD_SPIX: u32 = 75;
+pub const PI_CMD_SERO: u32 = 76;
+pub const PI_CMD_SERC: u32 = 77;
+pub const PI_CMD_SERRB: u32 = 78;
+pub const PI_CMD_SERWB: u32 = 79;
+pub const PI_CMD_SERR: u32 = 80;
+pub const PI_CMD_SERW: u32 = 81;
+pub const PI_CMD_SERDA: u32 = 82;
+pub const PI_CMD_GDC: u32 = 83;
+pub const PI_CMD_GPW: u32 = 84;
+pub const PI_CMD_HC: u32 = 85;
+pub const PI_CMD_HP: u32 = 86;
+pub const PI_CMD_CF1: u32 = 87;
+pub const PI_CMD_CF2: u32 = 88;
+pub const PI_CMD_BI2CC: u32 = 89;
+pub const PI_CMD_BI2CO: u32 = 90;
+pub const PI_CMD_BI2CZ: u32 = 91;
+pub const PI_CMD_I2CZ: u32 = 92;
+pub const PI_CMD_WVCHA: u32 = 93;
+pub const PI_CMD_SLRI: u32 = 94;
+pub const PI_CMD_CGI: u32 = 95;
+pub const PI_CMD_CSI: u32 = 96;
+pub const PI_CMD_FG: u32 = 97;
+pub const PI_CMD_FN: u32 = 98;
+pub const PI_CMD_NOIB: u32 = 99;
+pub const PI_CMD_WVTXM: u32 = 100;
+pub const PI_CMD_WVTAT: u32 = 101;
+pub const PI_CMD_PADS: u32 = 102;
+pub const PI_CMD_PADG: u32 = 103;
+pub const PI_CMD_FO: u32 = 104;
+pub const PI_CMD_FC: u32 = 105;
+pub const PI_CMD_FR: u32 = 106;
+pub const PI_CMD_FW: u32 = 107;
+pub const PI_CMD_FS: u32 = 108;
+pub const PI_CMD_FL: u32 = 109;
+pub const PI_CMD_SHELL: u32 = 110;
+pub const PI_CMD_BSPIC: u32 = 111;
+pub const PI_CMD_BSPIO: u32 = 112;
+pub const PI_CMD_BSPIX: u32 = 113;
+pub const PI_CMD_BSCX: u32 = 114;
+pub const PI_CMD_EVM: u32 = 115;
+pub const PI_CMD_EVT: u32 = 116;
+pub const PI_CMD_PROCU: u32 = 117;
+pub const PI_CMD_WVCAP: u32 = 118;
+
+pub const PI_INPUT: u32 = 0;
+pub const PI_OUTPUT: u32 = 1;
+pub const PI_ALT0: u32 = 4;
+pub const PI_ALT1: u32 = 5;
+pub const PI_ALT2: u32 = 6;
+pub const PI_ALT3: u32 = 7;
+pub const PI_ALT4: u32 = 3;
+pub const PI_ALT5: u32 = 2;
+pub const PI_LOW: u32 = 0;
+pub const PI_HIGH: u32 = 1;
+pub const PI_TIMEOUT: u32 = 2;
+pub const PI_PUD_OFF: u32 = 0;
+pub const PI_PUD_DOWN: u32 = 1;
+pub const PI_PUD_UP: u32 = 2;
+pub const RISING_EDGE: u32 = 0;
+pub const FALLING_EDGE: u32 = 1;
+pub const EITHER_EDGE: u32 = 2;
+pub const PI_MAX_USER_GPIO: u32 = 31;
+pub const PI_MAX_GPIO: u32 = 53;
+pub const PI_NTFY_FLAGS_EVENT: u32 = 1 << 7;
+pub const PI_NTFY_FLAGS_ALIVE: u32 = 1 << 6;
+pub const PI_NTFY_FLAGS_WDOG: u32 = 1 << 5;
+pub const PI_MAX_SCRIPT_PARAMS: u32 = 10;
+pub const PI_SCRIPT_INITING: u32 = 0;
+pub const PI_SCRIPT_HALTED: u32 = 1;
+pub const PI_SCRIPT_RUNNING: u32 = 2;
+pub const PI_SCRIPT_WAITING: u32 = 3;
+pub const PI_SCRIPT_FAILED: u32 = 4;
+pub const PI_FILE_READ: u32 = 1;
+pub const PI_FILE_WRITE: u32 = 2;
+pub const PI_FILE_RW: u32 = 3;
+pub const PI_FILE_APPEND: u32 = 4;
+pub const PI_FILE_CREATE: u32 = 8;
+pub const PI_FILE_TRUNC: u32 = 16;
+pub const PI_FROM_START: u32 = 0;
+pub const PI_FROM_CURRENT: u32 = 1;
+pub const PI_FROM_END: u32 = 2;
+pub const PI_MIN_DUTYCYCLE_RANGE: u32 = 25;
+pub const PI_MAX_DUTYCYCLE_RANGE: u32 = 40000;
+pub const PI_SERVO_OFF: u32 = 0;
+pub const PI_MIN_SERVO_PULSEWIDTH: u32 = 500;
+pub const PI_MAX_SERVO_PULSEWIDTH: u32 = 2500;
+pub const PI_HW_PWM_MIN_FREQ: u32 = 1;
+pub const PI_HW_PWM_MAX_FREQ: u32 = 125000000;
+pub const PI_HW_PWM_MAX_FREQ_2711: u32 = 187500000;
+pub const PI_HW_PWM_RANGE: u32 = 1000000;
+pub const PI_I2C_END: u32 = 0;
+pub const PI_I2C_ESC: u32 = 1;
+pub const PI_I2C_COMBINED_ON: u32 = 2;
+pub const PI_I2C_COMBINED_OFF: u32 = 3;
+pub const PI_I2C_START: u32 = 2;
+pub const PI_I2C_STOP: u32 = 3;
+pub const PI_I2C_ADDR: u32 = 4;
+pub const PI_I2C_FLAGS: u32 = 5;
+pub const PI_I2C_READ: u32 = 6;
+pub const PI_I2C_WRITE: u32 = 7;
+pub const PI_BB_I2C_MIN_BAUD: u32 = 50;
+pub const PI_BB_I2C_MAX_BAUD: u32 = 500000;
+pub const PI_BB_SER_NORMAL: u32 = 0;
+pub const PI_BB_SER_INVERT: u32 = 1;
+pub const PI_WAVE_MODE_ONE_SHOT: u32 = 0;
+pub const PI_WAVE_MODE_REPEAT: u32 = 1;
+pub const PI_WAVE_MODE_ONE_SHOT_SYNC: u32 = 2;
+pub const PI_WAVE_MODE_REPEAT_SYNC: u32 = 3;
+pub const PI_WAVE_NOT_FOUND: u32 = 9998;
+pub const PI_NO_TX_WAVE: u32 = 9999;
+pub const PI_MAX_WDOG_TIMEOUT: u32 = 60000;
+pub const PI_MAX_STEADY: u32 = 300000;
+pub const PI_MAX_ACTIVE: u32 = 1000000;
+pub const PI_MAX_PAD: u32 = 2;
+pub const PI_MIN_PAD_STRENGTH: u32 = 1;
+pub const PI_MAX_PAD_STRENGTH: u32 = 16;
+pub const PI_MAX_EVENT: u32 = 31;
+pub const PI_EVENT_BSC: u32 = 31;
+pub const BSC_FIFO_SIZE: u32 = 512;
+pub const PIGPIOD_IF2_VERSION: u32 = 17;
+
+pub const PI_INIT_FAILED: i32 = -1;
+pub const PI_BAD_USER_GPIO: i32 = -2;
+pub const PI_BAD_GPIO: i32 = -3;
+pub const PI_BAD_MODE: i32 = -4;
+pub const PI_BAD_LEVEL: i32 = -5;
+pub const PI_BAD_PUD: i32 = -6;
+pub const PI_BAD_PULSEWIDTH: i32 = -7;
+pub const PI_BAD_DUTYCYCLE: i32 = -8;
+pub const PI_BAD_TIMER: i32 = -9;
+pub const PI_BAD_MS: i32 = -10;
+pub const PI_BAD_TIMETYPE: i32 = -11;
+pub const PI_BAD_SECONDS: i32 = -12;
+pub const PI_BAD_MICROS: i32 = -13;
+pub const PI_TIMER_FAILED: i32 = -14;
+pub const PI_BAD_WDOG_TIMEOUT: i32 = -15;
+pub const PI_NO_ALERT_FUNC: i32 = -16;
+pub const PI_BAD_CLK_PERIPH: i32 = -17;
+pub const PI_BAD_CLK_SOURCE: i32 = -18;
+pub const PI_BAD_CLK_MICROS: i32 = -19;
+pub const PI_BAD_BUF_MILLIS: i32 = -20;
+pub const PI_BAD_DUTYRANGE: i32 = -21;
+pub const PI_BAD_SIGNUM: i32 = -22;
+pub const PI_BAD_PATHNAME: i32 = -23;
+pub const PI_NO_HANDLE: i32 = -24;
+pub const PI_BAD_HANDLE: i32 = -25;
+pub const PI_BAD_IF_FLAGS: i32 = -26;
+pub const PI_BAD_CHANNEL: i32 = -27;
+pub const PI_BAD_SOCKET_PORT: i32 = -28;
+pub const PI_BAD_FIFO_COMMAND: i32 = -29;
+pub const PI_BAD_SECO_CHANNEL: i32 = -30;
+pub const PI_NOT_INITIALISED: i32 = -31;
+pub const PI_INITIALISED: i32 = -32;
+pub const PI_BAD_WAVE_MODE: i32 = -33;
+pub const PI_BAD_CFG_INTERNAL: i32 = -34;
+pub const PI_BAD_WAVE_BAUD: i32 = -35;
+pub const PI_TOO_MANY_PULSES: i32 = -36;
+pub const PI_TOO_MANY_CHARS: i32 = -37;
+pub const PI_NOT_SERIAL_GPIO: i32 = -38;
+pub const PI_BAD_SERIAL_STRUC: i32 = -39;
+pub const PI_BAD_SERIAL_BUF: i32 = -40;
+pub const PI_NOT_PERMITTED: i32 = -41;
+pub const PI_SOME_PERMITTED: i32 = -42;
+pub const PI_BAD_WVSC_COMMND: i32 = -43;
+pub const PI_BAD_WVSM_COMMND: i32 = -44;
+pub const PI_BAD_WVSP_COMMND: i32 = -45;
+pub const PI_BAD_PULSELEN: i32 = -46;
+pub const PI_BAD_SCRIPT: i32 = -47;
+pub const PI_BAD_SCRIPT_ID: i32 = -48;
+pub const PI_BAD_SER_OFFSET: i32 = -49;
+pub const PI_GPIO_IN_USE: i32 = -50;
+pub const PI_BAD_SERIAL_COUNT: i32 = -51;
+pub const PI_BAD_PARAM_NUM: i32 = -52;
+pub const PI_DUP_TAG: i32 = -53;
+pub const PI_TOO_MANY_TAGS: i32 = -54;
+pub const PI_BAD_SCRIPT_CMD: i32 = -55;
+pub const PI_BAD_VAR_NUM: i32 = -56;
+pub const PI_NO_SCRIPT_ROOM: i32 = -57;
+pub const PI_NO_MEMORY: i32 = -58;
+pub const PI_SOCK_READ_FAILED: i32 = -59;
+pub const PI_SOCK_WRIT_FAILED: i32 = -60;
+pub const PI_TOO_MANY_PARAM: i32 = -61;
+pub const PI_SCRIPT_NOT_READY: i32 = -62;
+pub const PI_BAD_TAG: i32 = -63;
+pub const PI_BAD_MICS_DELAY: i32 = -64;
+pub const PI_BAD_MILS_DELAY: i32 = -65;
+pub const PI_BAD_WAVE_ID: i32 = -66;
+pub const PI_TOO_MANY_CBS: i32 = -67;
+pub const PI_TOO_MANY_OOL: i32 = -68;
+pub const PI_EMPTY_WAVEFORM: i32 = -69;
+pub const PI_NO_WAVEFORM_ID: i32 = -70;
+pub const PI_I2C_OPEN_FAILED: i32 = -71;
+pub const PI_SER_OPEN_FAILED: i32 = -72;
+pub const PI_SPI_OPEN_FAILED: i32 = -73;
+pub const PI_BAD_I2C_BUS: i32 = -74;
+pub const PI_BAD_I2C_ADDR: i32 = -75;
+pub const PI_BAD_SPI_CHANNEL: i32 = -76;
+pub const PI_BAD_FLAGS: i32 = -77;
+pub const PI_BAD_SPI_SPEED: i32 = -78;
+pub const PI_BAD_SER_DEVICE: i32 = -79;
+pub const PI_BAD_SER_SPEED: i32 = -80;
+pub const PI_BAD_PARAM: i32 = -81;
+pub const PI_I2C_WRITE_FAILED: i32 = -82;
+pub const PI_I2C_READ_FAILED: i32 = -83;
+pub const PI_BAD_SPI_COUNT: i32 = -84;
+pub const PI_SER_WRITE_FAILED: i32 = -85;
+pub const PI_SER_READ_FAILED: i32 = -86;
+pub const PI_SER_READ_NO_DATA: i32 = -87;
+pub const PI_UNKNOWN_COMMAND: i32 = -88;
+pub const PI_SPI_XFER_FAILED: i32 = -89;
+pub const PI_BAD_POINTER: i32 = -90;
+pub const PI_NO_AUX_SPI: i32 = -91;
+pub const PI_NOT_PWM_GPIO: i32 = -92;
+pub const PI_NOT_SERVO_GPIO: i32 = -93;
+pub const PI_NOT_HCLK_GPIO: i32 = -94;
+pub const PI_NOT_HPWM_GPIO: i32 = -95;
+pub const PI_BAD_HPWM_FREQ: i32 = -96;
+pub const PI_BAD_HPWM_DUTY: i32 = -97;
+pub const PI_BAD_HCLK_FREQ: i32 = -98;
+pub const PI_BAD_HCLK_PASS: i32 = -99;
+pub const PI_HPWM_ILLEGAL: i32 = -100;
+pub const PI_BAD_DATABITS: i32 = -101;
+pub const PI_BAD_STOPBITS: i32 = -102;
+pub const PI_MSG_TOOBIG: i32 = -103;
+pub const PI_BAD_MALLOC_MODE: i32 = -104;
+pub const PI_TOO_MANY_SEGS: i32 = -105;
+pub const PI_BAD_I2C_SEG: i32 = -106;
+pub const PI_BAD_SMBUS_CMD: i32 = -107;
+pub const PI_NOT_I2C_GPIO: i32 = -108;
+pub const PI_BAD_I2C_WLEN: i32 = -109;
+pub const PI_BAD_I2C_RLEN: i32 = -110;
+pub const PI_BAD_I2C_CMD: i32 = -111;
+pub const PI_BAD_I2C_BAUD: i32 = -112;
+pub const PI_CHAIN_LOOP_CNT: i32 = -113;
+pub const PI_BAD_CHAIN_LOOP: i32 = -114;
+pub const PI_CHAIN_COUNTER: i32 = -115;
+pub const PI_BAD_CHAIN_CMD: i32 = -116;
+pub const PI_BAD_CHAIN_DELAY: i32 = -117;
+pub const PI_CHAIN_NESTING: i32 = -118;
+pub const PI_CHAIN_TOO_BIG: i32 = -119;
+pub const PI_DEPRECATED: i32 = -120;
+pub const PI_BAD_SER_INVERT: i32 = -121;
+pub const PI_BAD_EDGE: i32 = -122;
+pub const PI_BAD_ISR_INIT: i32 = -123;
+pub const PI_BAD_FOREVER: i32 = -124;
+pub const PI_BAD_FILTER: i32 = -125;
+pub const PI_BAD_PAD: i32 = -126;
+pub const PI_BAD_STRENGTH: i32 = -127;
+pub const PI_FIL_OPEN_FAILED: i32 = -128;
+pub const PI_BAD_FILE_MODE: i32 = -129;
+pub const PI_BAD_FILE_FLAG: i32 = -130;
+pub const PI_BAD_FILE_READ: i32 = -131;
+pub const PI_BAD_FILE_WRITE: i32 = -132;
+pub const PI_FILE_NOT_ROPEN: i32 = -133;
+pub const PI_FILE_NOT_WOPEN: i32 = -134;
+pub const PI_BAD_FILE_SEEK: i32 = -135;
+pub const PI_NO_FILE_MATCH: i32 = -136;
+pub const PI_NO_FILE_ACCESS: i32 = -137;
+pub const PI_FILE_IS_A_DIR: i32 = -138;
+pub const PI_BAD_SHELL_STATUS: i32 = -139;
+pub const PI_BAD_SCRIPT_NAME: i32 = -140;
+pub const PI_BAD_SPI_BAUD: i32 = -141;
+pub const PI_NOT_SPI_GPIO: i32 = -142;
+pub const PI_BAD_EVENT_ID: i32 = -143;
+pub const PI_CMD_INTERRUPTED: i32 = -144;
+pub const PI_NOT_ON_BCM2711: i32 = -145;
+pub const PI_ONLY_ON_BCM2711: i32 = -146;
+
+pub const pigif_bad_send: i32 = -2000;
+pub const pigif_bad_recv: i32 = -2001;
+pub const pigif_bad_getaddrinfo: i32 = -2002;
+pub const pigif_bad_connect: i32 = -2003;
+pub const pigif_bad_socket: i32 = -2004;
+pub const pigif_bad_noib: i32 = -2005;
+pub const pigif_duplicate_callback: i32 = -2006;
+pub const pigif_bad_malloc: i32 = -2007;
+pub const pigif_bad_callback: i32 = -2008;
+pub const pigif_notify_failed: i32 = -2009;
+pub const pigif_callback_not_found: i32 = -2010;
+pub const pigif_unconnected_pi: i32 = -2011;
+pub const pigif_too_many_pis: i32 = -2012;
+
+// sent on a notification socket for every change of the monitored pins
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    pub seqno: u16,
+    pub flags: u16,
+    pub tick: u32,
+    pub level: u32,
+}
+
+impl Report {
+    pub const LEN: usize = 12;
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[0..2].copy_from_slice(&self.seqno.to_le_bytes());
+        bytes[2..4].copy_from_slice(&self.flags.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.tick.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.level.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::LEN]) -> Self {
+        Self {
+            seqno: u16::from_le_bytes([bytes[0], bytes[1]]),
+            flags: u16::from_le_bytes([bytes[2], bytes[3]]),
+            tick: u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            level: u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+        }
+    }
+
+    pub fn read_from<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; Self::LEN];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub cmd: u32,
+    pub p1: u32,
+    pub p2: u32,
+    pub p3: u32,
+}
+
+impl Header {
+    pub const LEN: usize = 16;
+
+    pub fn new(cmd: u32, p1: u32, p2: u32, p3: u32) -> Self {
+        Self { cmd, p1, p2, p3 }
+    }
+
+    // the last word of a response is the signed result
+    pub fn res(&self) -> i32 {
+        self.p3 as i32
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        for (chunk, word) in bytes
+            .chunks_exact_mut(4)
+            .zip([self.cmd, self.p1, self.p2, self.p3])
+        {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::LEN]) -> Self {
+        let mut words = bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+        // can't fail
+        let mut next = || words.next().unwrap();
+        Self::new(next(), next(), next(), next())
+    }
+
+    pub fn read_from<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; Self::LEN];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(bytes))
+    }
+
+    // header and extension are sent in one write so they end up in one segment
+    pub fn write_to<W: io::Write>(self, writer: &mut W, ext: &[u8]) -> io::Result<()> {
+        let mut buf = Vec::with_capacity(Self::LEN + ext.len());
+        buf.extend_from_slice(&self.to_bytes());
+        buf.extend_from_slice(ext);
+        writer.write_all(&buf)
+    }
+}
@@ -0,0 +1,224 @@
+use std::ffi;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Gpio, Init, Pi, Result};
+
+// the highest software PWM frequency pigpio has, at a 1µs sample rate
+const MAX_FREQUENCY: ffi::c_uint = 40_000;
+
+// fraction of the range set with `set_pwm_range`, 255 unless it was changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DutyCycle(ffi::c_uint);
+
+impl DutyCycle {
+    pub fn new(dutycycle: ffi::c_uint) -> Option<Self> {
+        // `set_pwm_dutycycle` checks it against the range of the pin
+        if dutycycle > pigpiod_if2::PI_MAX_DUTYCYCLE_RANGE {
+            return None;
+        }
+
+        Some(DutyCycle(dutycycle))
+    }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
+}
+
+// fraction of 1_000_000
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HwDutyCycle(ffi::c_uint);
+
+impl HwDutyCycle {
+    pub fn new(dutycycle: ffi::c_uint) -> Option<Self> {
+        if dutycycle > pigpiod_if2::PI_HW_PWM_RANGE {
+            return None;
+        }
+
+        Some(HwDutyCycle(dutycycle))
+    }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
+}
+
+// in Hz, the daemon picks the closest one its sample rate allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Frequency(ffi::c_uint);
+
+impl Frequency {
+    pub fn new(frequency: ffi::c_uint) -> Option<Self> {
+        if !(1..=MAX_FREQUENCY).contains(&frequency) {
+            return None;
+        }
+
+        Some(Frequency(frequency))
+    }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
+}
+
+// in Hz
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HwFrequency(ffi::c_uint);
+
+impl HwFrequency {
+    pub fn new(frequency: ffi::c_uint) -> Option<Self> {
+        // the highest the BCM2711 can do, older models stop at 125 MHz
+        if !(pigpiod_if2::PI_HW_PWM_MIN_FREQ..=pigpiod_if2::PI_HW_PWM_MAX_FREQ_2711)
+            .contains(&frequency)
+        {
+            return None;
+        }
+
+        Some(HwFrequency(frequency))
+    }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
+}
+
+// in microseconds, 0 switches the servo pulses off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PulseWidth(ffi::c_uint);
+
+impl PulseWidth {
+    pub const OFF: Self = PulseWidth(pigpiod_if2::PI_SERVO_OFF);
+
+    pub fn new(pulsewidth: ffi::c_uint) -> Option<Self> {
+        if pulsewidth != pigpiod_if2::PI_SERVO_OFF
+            && !(pigpiod_if2::PI_MIN_SERVO_PULSEWIDTH..=pigpiod_if2::PI_MAX_SERVO_PULSEWIDTH)
+                .contains(&pulsewidth)
+        {
+            return None;
+        }
+
+        Some(PulseWidth(pulsewidth))
+    }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
+}
+
+impl Pi<Init> {
+    pub fn set_pwm_dutycycle(&self, gpio: Gpio, dutycycle: DutyCycle) -> Result<()> {
+        if dutycycle.0 > self.get_pwm_range(gpio)? {
+            return Err(Error::Pi(ErrorCode::BadDutycycle));
+        }
+
+        let err = unsafe { pigpiod_if2::set_PWM_dutycycle(self.0 .0, gpio.0, dutycycle.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // returns the real range, which depends on the frequency
+    pub fn set_pwm_range(&self, gpio: Gpio, range: ffi::c_uint) -> Result<ffi::c_uint> {
+        let real = unsafe { pigpiod_if2::set_PWM_range(self.0 .0, gpio.0, range) };
+
+        if real.is_negative() {
+            return Err(Error::new(real));
+        }
+
+        Ok(real as ffi::c_uint)
+    }
+
+    // the daemon picks the closest frequency it supports and returns it
+    pub fn set_pwm_frequency(&self, gpio: Gpio, frequency: Frequency) -> Result<Frequency> {
+        let real = unsafe { pigpiod_if2::set_PWM_frequency(self.0 .0, gpio.0, frequency.0) };
+
+        if real.is_negative() {
+            return Err(Error::new(real));
+        }
+
+        Ok(Frequency(real as ffi::c_uint))
+    }
+
+    // only available on the pins wired to a PWM channel
+    pub fn hardware_pwm(
+        &self,
+        gpio: Gpio,
+        frequency: HwFrequency,
+        dutycycle: HwDutyCycle,
+    ) -> Result<()> {
+        let err = unsafe { pigpiod_if2::hardware_PWM(self.0 .0, gpio.0, frequency.0, dutycycle.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn set_servo_pulsewidth(&self, gpio: Gpio, pulsewidth: PulseWidth) -> Result<()> {
+        let err = unsafe { pigpiod_if2::set_servo_pulsewidth(self.0 .0, gpio.0, pulsewidth.0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // out of 1_000_000 while hardware PWM or a clock is running on the pin
+    pub fn get_pwm_dutycycle(&self, gpio: Gpio) -> Result<ffi::c_uint> {
+        let dutycycle = unsafe { pigpiod_if2::get_PWM_dutycycle(self.0 .0, gpio.0) };
+
+        if dutycycle.is_negative() {
+            return Err(Error::new(dutycycle));
+        }
+
+        Ok(dutycycle as ffi::c_uint)
+    }
+
+    // the range asked for with `set_pwm_range`
+    pub fn get_pwm_range(&self, gpio: Gpio) -> Result<ffi::c_uint> {
+        let range = unsafe { pigpiod_if2::get_PWM_range(self.0 .0, gpio.0) };
+
+        if range.is_negative() {
+            return Err(Error::new(range));
+        }
+
+        Ok(range as ffi::c_uint)
+    }
+
+    // the range actually used at the current frequency
+    pub fn get_pwm_real_range(&self, gpio: Gpio) -> Result<ffi::c_uint> {
+        let range = unsafe { pigpiod_if2::get_PWM_real_range(self.0 .0, gpio.0) };
+
+        if range.is_negative() {
+            return Err(Error::new(range));
+        }
+
+        Ok(range as ffi::c_uint)
+    }
+
+    // the hardware frequency while hardware PWM or a clock is running on the pin
+    pub fn get_pwm_frequency(&self, gpio: Gpio) -> Result<ffi::c_uint> {
+        let frequency = unsafe { pigpiod_if2::get_PWM_frequency(self.0 .0, gpio.0) };
+
+        if frequency.is_negative() {
+            return Err(Error::new(frequency));
+        }
+
+        Ok(frequency as ffi::c_uint)
+    }
+
+    pub fn get_servo_pulsewidth(&self, gpio: Gpio) -> Result<PulseWidth> {
+        let pulsewidth = unsafe { pigpiod_if2::get_servo_pulsewidth(self.0 .0, gpio.0) };
+
+        if pulsewidth.is_negative() {
+            return Err(Error::new(pulsewidth));
+        }
+
+        Ok(PulseWidth(pulsewidth as ffi::c_uint))
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections;
+use std::io;
+use std::path;
+use std::sync;
+use std::thread;
+use std::time;
+
+use crate::backend::GpioBackend;
+
+use super::{
+    Error, FileMode, Gpio, GpioLevel, GpioMode, Handle, Init, Pi, PiConnection, Pulse, Result,
+};
+
+// how long to wait between reconnection attempts, doubling up to `max`
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    initial: time::Duration,
+    max: time::Duration,
+    attempts: Option<u32>,
+}
+
+impl Backoff {
+    pub fn new(initial: time::Duration, max: time::Duration) -> Self {
+        Backoff {
+            initial,
+            max,
+            attempts: None,
+        }
+    }
+
+    // gives up after `attempts` failed reconnections instead of trying forever
+    pub fn attempts(self, attempts: u32) -> Self {
+        Self {
+            attempts: Some(attempts),
+            ..self
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(
+            time::Duration::from_millis(100),
+            time::Duration::from_secs(5),
+        )
+    }
+}
+
+// reconnects to pigpiod when the connection is lost and retries the failed call,
+// open handles like files don't survive a reconnection
+#[derive(Debug)]
+pub struct ReconnectingPi {
+    addr: String,
+    port: String,
+    pi: sync::RwLock<Pi<Init>>,
+    // applied again after reconnecting, pigpiod forgets them when it restarts
+    modes: sync::Mutex<collections::BTreeMap<Gpio, GpioMode>>,
+    backoff: Backoff,
+}
+
+impl ReconnectingPi {
+    pub fn new(addr: &str, port: &str) -> Result<Self> {
+        let pi = Pi::try_with_addr_and_port(addr, port)?;
+
+        Ok(Self {
+            addr: addr.to_owned(),
+            port: port.to_owned(),
+            pi: sync::RwLock::new(pi),
+            modes: sync::Mutex::default(),
+            backoff: Backoff::default(),
+        })
+    }
+
+    pub fn backoff(self, backoff: Backoff) -> Self {
+        Self { backoff, ..self }
+    }
+
+    // runs `f` on the current connection, reconnecting and running it again
+    // for as long as it fails because the daemon can't be reached
+    pub fn with<T, F>(&self, mut f: F) -> Result<T>
+    where
+        F: FnMut(&Pi<Init>) -> Result<T>,
+    {
+        let mut delay = self.backoff.initial;
+        let mut attempt = 0;
+
+        loop {
+            let res = f(&self.pi());
+            let mut err = match res {
+                Err(err) if is_disconnected(&err) => err,
+                res => return res,
+            };
+
+            loop {
+                if self
+                    .backoff
+                    .attempts
+                    .is_some_and(|attempts| attempt >= attempts)
+                {
+                    return Err(err);
+                }
+                attempt += 1;
+
+                thread::sleep(delay);
+                delay = (delay * 2).min(self.backoff.max);
+
+                match self.reconnect() {
+                    Ok(()) => break,
+                    Err(reconnect_err) if is_disconnected(&reconnect_err) => err = reconnect_err,
+                    Err(reconnect_err) => return Err(reconnect_err),
+                }
+            }
+        }
+    }
+
+    fn reconnect(&self) -> Result<()> {
+        let mut pi = self
+            .pi
+            .write()
+            .unwrap_or_else(sync::PoisonError::into_inner);
+
+        let new = Pi::try_with_addr_and_port(&self.addr, &self.port)?;
+        for (&gpio, &mode) in self.modes().iter() {
+            new.set_mode(gpio, mode)?;
+        }
+
+        // stops the old connection
+        *pi = new;
+        Ok(())
+    }
+
+    fn modes(&self) -> sync::MutexGuard<'_, collections::BTreeMap<Gpio, GpioMode>> {
+        self.modes
+            .lock()
+            .unwrap_or_else(sync::PoisonError::into_inner)
+    }
+}
+
+fn is_disconnected(error: &Error) -> bool {
+    error.code().is_some_and(|code| code.is_disconnected())
+}
+
+// peripherals on it use whichever connection is current and scripts retry
+// their calls, handles and script ids are gone if the daemon restarted though
+impl PiConnection for ReconnectingPi {
+    type Guard<'a> = sync::RwLockReadGuard<'a, Pi<Init>>;
+
+    fn pi(&self) -> Self::Guard<'_> {
+        self.pi.read().unwrap_or_else(sync::PoisonError::into_inner)
+    }
+
+    fn with<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnMut(&Pi<Init>) -> Result<T>,
+    {
+        ReconnectingPi::with(self, f)
+    }
+}
+
+impl GpioBackend for ReconnectingPi {
+    fn set_mode(&self, gpio: Gpio, mode: GpioMode) -> Result<()> {
+        self.with(|pi| pi.set_mode(gpio, mode))?;
+        self.modes().insert(gpio, mode);
+        Ok(())
+    }
+
+    fn gpio_write(&self, gpio: Gpio, level: GpioLevel) -> Result<()> {
+        self.with(|pi| pi.gpio_write(gpio, level))
+    }
+
+    fn gpio_read(&self, gpio: Gpio) -> Result<GpioLevel> {
+        self.with(|pi| pi.gpio_read(gpio))
+    }
+
+    fn file_open(&self, path: &path::Path, mode: FileMode) -> Result<Handle> {
+        self.with(|pi| pi.file_open(path, mode))
+    }
+
+    fn file_read(&self, handle: &Handle, buf: &mut [u8]) -> Result<usize> {
+        self.with(|pi| pi.file_read(handle, buf))
+    }
+
+    fn file_write(&self, handle: &Handle, buf: &[u8]) -> Result<()> {
+        self.with(|pi| pi.file_write(handle, buf))
+    }
+
+    fn file_seek(&self, handle: &Handle, pos: io::SeekFrom) -> Result<u64> {
+        self.with(|pi| pi.file_seek(handle, pos))
+    }
+
+    fn file_list(&self, pattern: &str) -> Result<Vec<path::PathBuf>> {
+        self.with(|pi| pi.file_list(pattern))
+    }
+
+    fn file_close(&self, handle: &Handle) {
+        self.pi().file_close(handle)
+    }
+
+    fn send_pulses(&self, pulses: &[Pulse]) -> Result<()> {
+        self.with(|pi| pi.send_pulses(pulses))
+    }
+
+    // a tick from after reconnecting can't be compared with one from
+    // before, so there's only one attempt
+    fn current_tick(&self) -> Result<u32> {
+        Ok(self.pi().current_tick()?.get())
+    }
+}
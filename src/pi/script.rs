@@ -0,0 +1,174 @@
+use std::ffi;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Init, Pi, PiConnection, Result};
+
+// parameters p0-p9 of a script
+pub type ScriptParams = [u32; pigpiod_if2::PI_MAX_SCRIPT_PARAMS as usize];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptStatus {
+    Initing,
+    Halted,
+    Running,
+    Waiting,
+    Failed,
+}
+
+impl ScriptStatus {
+    fn from_code(status: ffi::c_uint) -> Self {
+        match status {
+            pigpiod_if2::PI_SCRIPT_INITING => ScriptStatus::Initing,
+            pigpiod_if2::PI_SCRIPT_HALTED => ScriptStatus::Halted,
+            pigpiod_if2::PI_SCRIPT_RUNNING => ScriptStatus::Running,
+            pigpiod_if2::PI_SCRIPT_WAITING => ScriptStatus::Waiting,
+            _ => ScriptStatus::Failed,
+        }
+    }
+}
+
+// a script stored on the daemon, stopped and deleted when dropped
+#[derive(Debug)]
+pub struct Script<C: PiConnection = Pi<Init>> {
+    pi: C,
+    id: ffi::c_uint,
+}
+
+impl<C: PiConnection> Script<C> {
+    // the daemon checks the script in the background, it can't be run
+    // before `status` stops reporting `Initing`
+    pub fn store(pi: C, source: &str) -> Result<Self> {
+        let id = pi.with(|pi| store(pi, source))?;
+        Ok(Self { pi, id })
+    }
+
+    // stores `source` again under a new id, e.g. after the daemon restarted
+    // and forgot it, the old id is deleted first in case it didn't
+    pub fn restore(&mut self, source: &str) -> Result<()> {
+        self.id = self.pi.with(|pi| {
+            let err = unsafe { pigpiod_if2::delete_script(pi.0 .0, self.id) };
+
+            if err.is_negative() {
+                match Error::new(err) {
+                    Error::Pi(ErrorCode::BadScriptId) => {}
+                    error => return Err(error),
+                }
+            }
+
+            store(pi, source)
+        })?;
+        Ok(())
+    }
+
+    // `params` fill p0 onwards, the rest keep their values
+    pub fn run(&self, params: &[u32]) -> Result<()> {
+        let (numpar, pparams) = Self::params(params)?;
+
+        self.pi.with(|pi| {
+            let err = unsafe { pigpiod_if2::run_script(pi.0 .0, self.id, numpar, pparams) };
+
+            if err.is_negative() {
+                return Err(Error::new(err));
+            }
+
+            Ok(())
+        })
+    }
+
+    // changes the parameters of a running script
+    pub fn update(&self, params: &[u32]) -> Result<()> {
+        let (numpar, pparams) = Self::params(params)?;
+
+        self.pi.with(|pi| {
+            let err = unsafe { pigpiod_if2::update_script(pi.0 .0, self.id, numpar, pparams) };
+
+            if err.is_negative() {
+                return Err(Error::new(err));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn status(&self) -> Result<(ScriptStatus, ScriptParams)> {
+        self.pi.with(|pi| {
+            let mut params = ScriptParams::default();
+
+            let status =
+                unsafe { pigpiod_if2::script_status(pi.0 .0, self.id, params.as_mut_ptr()) };
+
+            if status.is_negative() {
+                return Err(Error::new(status));
+            }
+
+            Ok((ScriptStatus::from_code(status as ffi::c_uint), params))
+        })
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        self.pi.with(|pi| {
+            let err = unsafe { pigpiod_if2::stop_script(pi.0 .0, self.id) };
+
+            if err.is_negative() {
+                return Err(Error::new(err));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn delete(self) {}
+
+    fn params(params: &[u32]) -> Result<(ffi::c_uint, *mut u32)> {
+        if params.len() > pigpiod_if2::PI_MAX_SCRIPT_PARAMS as usize {
+            return Err(Error::Pi(ErrorCode::TooManyParam));
+        }
+
+        Ok((params.len() as ffi::c_uint, params.as_ptr().cast_mut()))
+    }
+}
+
+fn store(pi: &Pi<Init>, source: &str) -> Result<ffi::c_uint> {
+    let sourcestr = ffi::CString::new(source)?;
+    let psource = sourcestr.as_ptr().cast_mut();
+
+    let id = unsafe { pigpiod_if2::store_script(pi.0 .0, psource) };
+
+    if id.is_negative() {
+        return Err(Error::new(id));
+    }
+
+    Ok(id as ffi::c_uint)
+}
+
+impl<C: PiConnection> Drop for Script<C> {
+    fn drop(&mut self) {
+        unsafe {
+            pigpiod_if2::stop_script(self.pi.pi().0 .0, self.id);
+            pigpiod_if2::delete_script(self.pi.pi().0 .0, self.id);
+        }
+    }
+}
+
+impl Pi<Init> {
+    // runs /opt/pigpio/cgi/`name` with `args` on the Pi, only scripts placed
+    // in that directory can be run, returns the status like system(3) does
+    pub fn shell(&self, name: &str, args: &str) -> Result<ffi::c_int> {
+        let namestr = ffi::CString::new(name)?;
+        let argsstr = ffi::CString::new(args)?;
+
+        let status = unsafe {
+            pigpiod_if2::shell_(
+                self.0 .0,
+                namestr.as_ptr().cast_mut(),
+                argsstr.as_ptr().cast_mut(),
+            )
+        };
+
+        if status.is_negative() {
+            return Err(Error::new(status));
+        }
+
+        Ok(status)
+    }
+}
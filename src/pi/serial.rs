@@ -0,0 +1,161 @@
+use std::ffi;
+use std::io;
+use std::thread;
+use std::time;
+
+use super::pigpiod_if2;
+use super::{Error, Handle, Init, Pi, PiConnection, Result};
+
+// how often a blocking read asks the daemon for new data
+const POLL_INTERVAL: time::Duration = time::Duration::from_millis(10);
+
+// closed when dropped
+#[derive(Debug)]
+pub struct Serial<C: PiConnection = Pi<Init>> {
+    pi: C,
+    handle: Handle,
+    timeout: Option<time::Duration>,
+}
+
+impl<C: PiConnection> Serial<C> {
+    // `tty` has to be one of /dev/serial* or /dev/tty* on the Pi, e.g. /dev/serial0
+    pub fn open(pi: C, tty: &str, baud: ffi::c_uint) -> Result<Self> {
+        let ttystr = ffi::CString::new(tty)?;
+        let ptty = ttystr.as_ptr().cast_mut();
+
+        // pigpio has no serial flags yet, they have to be 0
+        let handle = unsafe { pigpiod_if2::serial_open(pi.pi().0 .0, ptty, baud, 0) };
+
+        if handle.is_negative() {
+            return Err(Error::new(handle));
+        }
+
+        Ok(Self {
+            pi,
+            handle: Handle(handle as ffi::c_uint),
+            timeout: None,
+        })
+    }
+
+    pub fn close(self) {}
+
+    // limits how long `io::Read` waits for data, forever if `None`
+    pub fn set_read_timeout(&mut self, timeout: Option<time::Duration>) {
+        self.timeout = timeout;
+    }
+
+    // doesn't wait, 0 if nothing has been received
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let pbuf = buf.as_mut_ptr().cast();
+        let buflen = buf.len() as ffi::c_uint;
+
+        let count =
+            unsafe { pigpiod_if2::serial_read(self.pi.pi().0 .0, self.handle.0, pbuf, buflen) };
+
+        // pigpio reports an empty buffer as an error
+        if count == pigpiod_if2::PI_SER_READ_NO_DATA {
+            return Ok(0);
+        }
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn write(&self, bytes: &[u8]) -> Result<()> {
+        let pbuf = bytes.as_ptr().cast_mut().cast();
+        let buflen = bytes.len() as ffi::c_uint;
+
+        let err =
+            unsafe { pigpiod_if2::serial_write(self.pi.pi().0 .0, self.handle.0, pbuf, buflen) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // doesn't wait, `None` if nothing has been received
+    pub fn read_byte(&self) -> Result<Option<u8>> {
+        let byte = unsafe { pigpiod_if2::serial_read_byte(self.pi.pi().0 .0, self.handle.0) };
+
+        if byte == pigpiod_if2::PI_SER_READ_NO_DATA {
+            return Ok(None);
+        }
+
+        if byte.is_negative() {
+            return Err(Error::new(byte));
+        }
+
+        Ok(Some(byte as u8))
+    }
+
+    pub fn write_byte(&self, byte: u8) -> Result<()> {
+        let err = unsafe {
+            pigpiod_if2::serial_write_byte(self.pi.pi().0 .0, self.handle.0, byte as ffi::c_uint)
+        };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn data_available(&self) -> Result<usize> {
+        let count = unsafe { pigpiod_if2::serial_data_available(self.pi.pi().0 .0, self.handle.0) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+}
+
+// blocks until at least one byte has been received, a 0 would mean EOF
+impl<C: PiConnection> io::Read for Serial<C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let deadline = self
+            .timeout
+            .and_then(|timeout| time::Instant::now().checked_add(timeout));
+
+        loop {
+            let count = Serial::read(self, buf)?;
+            if count > 0 {
+                return Ok(count);
+            }
+
+            if deadline.is_some_and(|deadline| time::Instant::now() >= deadline) {
+                return Err(io::ErrorKind::TimedOut.into());
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+impl<C: PiConnection> io::Write for Serial<C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Serial::write(self, buf)?;
+        Ok(buf.len())
+    }
+
+    // the daemon writes straight through
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<C: PiConnection> Drop for Serial<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::serial_close(self.pi.pi().0 .0, self.handle.0) };
+    }
+}
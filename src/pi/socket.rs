@@ -0,0 +1,1642 @@
+// pure Rust client for the pigpiod socket interface
+//
+// mirrors the pigpiod_if2 C interface, including its integer connection ids
+// and raw pointer arguments, so the rest of the pi module works unchanged on
+// top of either implementation
+
+// names follow the C library
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+use std::env;
+use std::ffi;
+use std::io;
+use std::net;
+use std::result;
+use std::slice;
+use std::sync;
+use std::sync::atomic;
+use std::thread;
+use std::time;
+
+pub use super::protocol::*;
+
+// same limit as the C library
+const MAX_PI: usize = 32;
+
+static PIS: sync::Mutex<Vec<Option<sync::Arc<Connection>>>> = sync::Mutex::new(Vec::new());
+
+fn lock<T>(mutex: &sync::Mutex<T>) -> sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(sync::PoisonError::into_inner)
+}
+
+#[derive(Debug)]
+struct Connection {
+    addr: net::SocketAddr,
+    stream: sync::Mutex<net::TcpStream>,
+    // opened with the first callback
+    notify: sync::Mutex<Option<Notify>>,
+}
+
+#[derive(Debug)]
+struct Notify {
+    handle: u32,
+    stream: net::TcpStream,
+    monitor: sync::Arc<sync::Mutex<Monitor>>,
+}
+
+// shared with the thread dispatching the reports
+#[derive(Debug, Default)]
+struct Monitor {
+    callbacks: Vec<Callback>,
+    events: Vec<EventCallback>,
+    // levels as of the last report, edges are derived from it
+    last: u32,
+    // events the daemon was last told to report
+    event_bits: u32,
+}
+
+#[derive(Debug)]
+struct Callback {
+    id: ffi::c_uint,
+    gpio: u32,
+    edge: u32,
+    f: CBFuncEx_t,
+    userdata: Userdata,
+}
+
+#[derive(Debug)]
+struct EventCallback {
+    id: ffi::c_uint,
+    event: u32,
+    f: evtCBFuncEx_t,
+    userdata: Userdata,
+}
+
+// only ever handed back to the function it was registered with
+#[derive(Debug)]
+struct Userdata(*mut ffi::c_void);
+
+unsafe impl Send for Userdata {}
+
+static NEXT_CALLBACK: atomic::AtomicU32 = atomic::AtomicU32::new(0);
+
+pub type CBFuncEx_t = Option<
+    unsafe extern "C" fn(
+        pi: ffi::c_int,
+        user_gpio: ffi::c_uint,
+        level: ffi::c_uint,
+        tick: u32,
+        userdata: *mut ffi::c_void,
+    ),
+>;
+
+pub type evtCBFuncEx_t = Option<
+    unsafe extern "C" fn(pi: ffi::c_int, event: ffi::c_uint, tick: u32, userdata: *mut ffi::c_void),
+>;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct gpioPulse_t {
+    pub gpioOn: u32,
+    pub gpioOff: u32,
+    pub usDelay: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct bsc_xfer_t {
+    pub control: u32,
+    pub rxCnt: ffi::c_int,
+    pub rxBuf: [ffi::c_char; 512],
+    pub txCnt: ffi::c_int,
+    pub txBuf: [ffi::c_char; 512],
+}
+
+impl Connection {
+    fn command(&self, cmd: u32, p1: u32, p2: u32, ext: &[u8], buf: Option<&mut [u8]>) -> i32 {
+        self.exchange(cmd, p1, p2, ext, buf)
+            .unwrap_or_else(|err| err)
+    }
+
+    // keeps transport errors apart from the daemon's answer, which can be
+    // any value for some commands
+    fn exchange(
+        &self,
+        cmd: u32,
+        p1: u32,
+        p2: u32,
+        ext: &[u8],
+        buf: Option<&mut [u8]>,
+    ) -> result::Result<i32, i32> {
+        use io::Read;
+
+        let mut stream = lock(&self.stream);
+
+        let request = Header::new(cmd, p1, p2, ext.len() as u32);
+        if request.write_to(&mut *stream, ext).is_err() {
+            return Err(pigif_bad_send);
+        }
+
+        let res = match Header::read_from(&mut *stream) {
+            Ok(response) => response.res(),
+            Err(_) => return Err(pigif_bad_recv),
+        };
+
+        // extended responses carry `res` bytes, keep what fits and drop the rest
+        match buf {
+            Some(buf) if res > 0 => {
+                let count = buf.len().min(res as usize);
+                if stream.read_exact(&mut buf[..count]).is_err() {
+                    return Err(pigif_bad_recv);
+                }
+                let rest = res as u64 - count as u64;
+                if io::copy(&mut (&mut *stream).take(rest), &mut io::sink()).is_err() {
+                    return Err(pigif_bad_recv);
+                }
+                Ok(count as i32)
+            }
+            _ => Ok(res),
+        }
+    }
+}
+
+impl Connection {
+    fn start_notify(&self, pi: ffi::c_int) -> Result<Notify, ffi::c_int> {
+        let mut stream = net::TcpStream::connect(self.addr).map_err(|_| pigif_bad_connect)?;
+
+        // turns the new socket into a stream of reports
+        Header::new(PI_CMD_NOIB, 0, 0, 0)
+            .write_to(&mut stream, &[])
+            .map_err(|_| pigif_bad_send)?;
+        let handle = Header::read_from(&mut stream)
+            .map_err(|_| pigif_bad_recv)?
+            .res();
+        if handle.is_negative() {
+            return Err(pigif_bad_noib);
+        }
+
+        let monitor = sync::Arc::<sync::Mutex<Monitor>>::default();
+
+        let reader = stream.try_clone().map_err(|_| pigif_bad_socket)?;
+        let dispatched = monitor.clone();
+        thread::Builder::new()
+            .spawn(move || dispatch(pi, reader, &dispatched))
+            .map_err(|_| pigif_notify_failed)?;
+
+        Ok(Notify {
+            handle: handle as u32,
+            stream,
+            monitor,
+        })
+    }
+
+    // tells the daemon which pins and events the callbacks are interested in
+    fn update_notify(&self, notify: &Notify) -> ffi::c_int {
+        let mut monitor = lock(&notify.monitor);
+        let bits = monitor
+            .callbacks
+            .iter()
+            .fold(0, |bits, callback| bits | 1 << callback.gpio);
+        let event_bits = monitor
+            .events
+            .iter()
+            .fold(0, |bits, callback| bits | 1 << callback.event);
+
+        // only bothers the daemon with events once they are used
+        if event_bits != monitor.event_bits {
+            let err = self.command(PI_CMD_EVM, notify.handle, event_bits, &[], None);
+            if err.is_negative() {
+                return err;
+            }
+            monitor.event_bits = event_bits;
+        }
+
+        // pins nobody watched didn't get reported, so the last levels may be stale
+        let level = self.command(PI_CMD_BR1, 0, 0, &[], None);
+        if level.is_negative() {
+            return level;
+        }
+        monitor.last = level as u32;
+
+        self.command(PI_CMD_NB, notify.handle, bits, &[], None)
+    }
+}
+
+fn dispatch(pi: ffi::c_int, mut stream: net::TcpStream, monitor: &sync::Mutex<Monitor>) {
+    while let Ok(report) = Report::read_from(&mut stream) {
+        let flags = report.flags as u32;
+
+        // called once the monitor is unlocked, they may register or cancel
+        // callbacks themselves
+        let mut edges = Vec::new();
+        let mut events = Vec::new();
+
+        {
+            let mut monitor = lock(monitor);
+
+            if flags & PI_NTFY_FLAGS_EVENT != 0 {
+                let event = flags & PI_MAX_EVENT;
+
+                events.extend(
+                    monitor
+                        .events
+                        .iter()
+                        .filter(|callback| callback.event == event)
+                        .filter_map(|callback| Some((callback.f?, event, callback.userdata.0))),
+                );
+            } else if flags == 0 {
+                let changed = report.level ^ monitor.last;
+                monitor.last = report.level;
+
+                for callback in monitor.callbacks.iter() {
+                    if changed & 1 << callback.gpio == 0 {
+                        continue;
+                    }
+
+                    let level = report.level >> callback.gpio & 1;
+                    let wanted = match callback.edge {
+                        RISING_EDGE => level == PI_HIGH,
+                        FALLING_EDGE => level == PI_LOW,
+                        _ => true,
+                    };
+
+                    if let (true, Some(f)) = (wanted, callback.f) {
+                        edges.push((f, callback.gpio, level, callback.userdata.0));
+                    }
+                }
+            } else if flags & PI_NTFY_FLAGS_WDOG != 0 {
+                let gpio = flags & PI_MAX_USER_GPIO;
+
+                edges.extend(
+                    monitor
+                        .callbacks
+                        .iter()
+                        .filter(|callback| callback.gpio == gpio)
+                        .filter_map(|callback| {
+                            Some((callback.f?, gpio, PI_TIMEOUT, callback.userdata.0))
+                        }),
+                );
+            }
+        }
+
+        for (f, gpio, level, userdata) in edges {
+            unsafe { f(pi, gpio, level, report.tick, userdata) };
+        }
+        for (f, event, userdata) in events {
+            unsafe { f(pi, event, report.tick, userdata) };
+        }
+    }
+}
+
+// opens the notification socket with the first callback, `register` adds the
+// callback under the id it is given
+fn register<F>(pi: ffi::c_int, register: F) -> ffi::c_int
+where
+    F: FnOnce(&mut Monitor, ffi::c_uint),
+{
+    let Some(conn) = connection(pi) else {
+        return pigif_unconnected_pi;
+    };
+
+    let mut notify = lock(&conn.notify);
+    if notify.is_none() {
+        match conn.start_notify(pi) {
+            Ok(started) => *notify = Some(started),
+            Err(err) => return err,
+        }
+    }
+    // can't fail
+    let notify = notify.as_ref().unwrap();
+
+    let id = NEXT_CALLBACK.fetch_add(1, atomic::Ordering::Relaxed);
+    register(&mut lock(&notify.monitor), id);
+
+    let err = conn.update_notify(notify);
+    if err.is_negative() {
+        return err;
+    }
+
+    id as ffi::c_int
+}
+
+// `remove` returns whether it found the callback
+fn unregister<F>(remove: F) -> ffi::c_int
+where
+    F: Fn(&mut Monitor) -> bool,
+{
+    // don't hold on to the table, callbacks may be running and using it
+    let conns = lock(&PIS).iter().flatten().cloned().collect::<Vec<_>>();
+
+    for conn in conns {
+        let notify = lock(&conn.notify);
+        let Some(notify) = notify.as_ref() else {
+            continue;
+        };
+
+        let removed = remove(&mut lock(&notify.monitor));
+
+        if removed {
+            conn.update_notify(notify);
+            return 0;
+        }
+    }
+
+    pigif_callback_not_found
+}
+
+fn command(pi: ffi::c_int, cmd: u32, p1: u32, p2: u32, ext: &[u8]) -> ffi::c_int {
+    match connection(pi) {
+        Some(conn) => conn.command(cmd, p1, p2, ext, None),
+        None => pigif_unconnected_pi,
+    }
+}
+
+fn command_recv(
+    pi: ffi::c_int,
+    cmd: u32,
+    p1: u32,
+    p2: u32,
+    ext: &[u8],
+    buf: &mut [u8],
+) -> ffi::c_int {
+    match connection(pi) {
+        Some(conn) => conn.command(cmd, p1, p2, ext, Some(buf)),
+        None => pigif_unconnected_pi,
+    }
+}
+
+// for the commands whose every answer is a valid value, like the tick or the
+// bank levels, unlike with the C library a lost connection can't pass for one
+pub(super) fn command_u32(pi: ffi::c_int, cmd: u32) -> result::Result<u32, ffi::c_int> {
+    match connection(pi) {
+        Some(conn) => conn.exchange(cmd, 0, 0, &[], None).map(|res| res as u32),
+        None => Err(pigif_unconnected_pi),
+    }
+}
+
+fn connection(pi: ffi::c_int) -> Option<sync::Arc<Connection>> {
+    let pis = lock(&PIS);
+    let pi = usize::try_from(pi).ok()?;
+    pis.get(pi).cloned().flatten()
+}
+
+// like the C library, a null or empty argument falls back to the
+// environment and then to the defaults
+unsafe fn arg_or_env(arg: *const ffi::c_char, var: &str, default: &str) -> String {
+    if !arg.is_null() {
+        let arg = ffi::CStr::from_ptr(arg).to_string_lossy();
+        if !arg.is_empty() {
+            return arg.into_owned();
+        }
+    }
+
+    env::var(var)
+        .ok()
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| default.to_owned())
+}
+
+pub unsafe fn pigpio_start(
+    addr_str: *const ffi::c_char,
+    port_str: *const ffi::c_char,
+) -> ffi::c_int {
+    let addr = arg_or_env(addr_str, "PIGPIO_ADDR", "localhost");
+    let port = arg_or_env(port_str, "PIGPIO_PORT", "8888");
+
+    let Ok(port) = port.parse::<u16>() else {
+        return pigif_bad_getaddrinfo;
+    };
+
+    let Ok(addrs) = net::ToSocketAddrs::to_socket_addrs(&(addr.as_str(), port)) else {
+        return pigif_bad_getaddrinfo;
+    };
+
+    let Ok(stream) = net::TcpStream::connect(&addrs.collect::<Vec<_>>()[..]) else {
+        return pigif_bad_connect;
+    };
+
+    // commands are tiny, don't let Nagle batch them
+    if stream.set_nodelay(true).is_err() {
+        return pigif_bad_socket;
+    }
+
+    let Ok(addr) = stream.peer_addr() else {
+        return pigif_bad_socket;
+    };
+
+    let conn = sync::Arc::new(Connection {
+        addr,
+        stream: sync::Mutex::new(stream),
+        notify: sync::Mutex::new(None),
+    });
+
+    let mut pis = lock(&PIS);
+    match pis.iter().position(Option::is_none) {
+        Some(pi) => {
+            pis[pi] = Some(conn);
+            pi as ffi::c_int
+        }
+        None if pis.len() < MAX_PI => {
+            pis.push(Some(conn));
+            (pis.len() - 1) as ffi::c_int
+        }
+        None => pigif_too_many_pis,
+    }
+}
+
+pub unsafe fn pigpio_stop(pi: ffi::c_int) {
+    let conn = {
+        let mut pis = lock(&PIS);
+        let slot = usize::try_from(pi).ok().and_then(|pi| pis.get_mut(pi));
+        slot.and_then(Option::take)
+    };
+
+    let Some(conn) = conn else {
+        return;
+    };
+
+    if let Some(notify) = lock(&conn.notify).take() {
+        conn.command(PI_CMD_NC, notify.handle, 0, &[], None);
+        let _ = notify.stream.shutdown(net::Shutdown::Both);
+    }
+
+    let _ = lock(&conn.stream).shutdown(net::Shutdown::Both);
+}
+
+pub unsafe fn pigpio_error(errnum: ffi::c_int) -> *const ffi::c_char {
+    let error = match errnum {
+        PI_INIT_FAILED => c"pigpio initialisation failed",
+        PI_BAD_USER_GPIO => c"GPIO not 0-31",
+        PI_BAD_GPIO => c"GPIO not 0-53",
+        PI_BAD_MODE => c"mode not 0-7",
+        PI_BAD_LEVEL => c"level not 0-1",
+        PI_BAD_PUD => c"pud not 0-2",
+        PI_BAD_PULSEWIDTH => c"pulsewidth not 0 or 500-2500",
+        PI_BAD_DUTYCYCLE => c"dutycycle not 0-range (default 255)",
+        PI_BAD_TIMER => c"timer not 0-9",
+        PI_BAD_MS => c"ms not 10-60000",
+        PI_BAD_TIMETYPE => c"timetype not 0-1",
+        PI_BAD_SECONDS => c"seconds < 0",
+        PI_BAD_MICROS => c"micros not 0-999999",
+        PI_TIMER_FAILED => c"gpioSetTimerFunc failed",
+        PI_BAD_WDOG_TIMEOUT => c"timeout not 0-60000",
+        PI_NO_ALERT_FUNC => c"DEPRECATED",
+        PI_BAD_CLK_PERIPH => c"clock peripheral not 0-1",
+        PI_BAD_CLK_SOURCE => c"DEPRECATED",
+        PI_BAD_CLK_MICROS => c"clock micros not 1, 2, 4, 5, 8, or 10",
+        PI_BAD_BUF_MILLIS => c"buf millis not 100-10000",
+        PI_BAD_DUTYRANGE => c"dutycycle range not 25-40000",
+        PI_BAD_SIGNUM => c"signum not 0-63",
+        PI_BAD_PATHNAME => c"can't open pathname",
+        PI_NO_HANDLE => c"no handle available",
+        PI_BAD_HANDLE => c"unknown handle",
+        PI_BAD_IF_FLAGS => c"ifFlags > 4",
+        PI_BAD_CHANNEL => c"DMA channel not 0-15",
+        PI_BAD_SOCKET_PORT => c"socket port not 1024-30000",
+        PI_BAD_FIFO_COMMAND => c"unknown fifo command",
+        PI_BAD_SECO_CHANNEL => c"DMA secondary channel not 0-15",
+        PI_NOT_INITIALISED => c"function called before gpioInitialise",
+        PI_INITIALISED => c"function called after gpioInitialise",
+        PI_BAD_WAVE_MODE => c"waveform mode not 0-3",
+        PI_BAD_CFG_INTERNAL => c"bad parameter in gpioCfgInternals call",
+        PI_BAD_WAVE_BAUD => c"baud rate not 50-250K(RX)/50-1M(TX)",
+        PI_TOO_MANY_PULSES => c"waveform has too many pulses",
+        PI_TOO_MANY_CHARS => c"waveform has too many chars",
+        PI_NOT_SERIAL_GPIO => c"no bit bang serial read in progress on GPIO",
+        PI_BAD_SERIAL_STRUC => c"bad (null) serial structure parameter",
+        PI_BAD_SERIAL_BUF => c"bad (null) serial buf parameter",
+        PI_NOT_PERMITTED => c"no permission to update GPIO",
+        PI_SOME_PERMITTED => c"no permission to update one or more GPIO",
+        PI_BAD_WVSC_COMMND => c"bad WVSC subcommand",
+        PI_BAD_WVSM_COMMND => c"bad WVSM subcommand",
+        PI_BAD_WVSP_COMMND => c"bad WVSP subcommand",
+        PI_BAD_PULSELEN => c"trigger pulse length not 1-100",
+        PI_BAD_SCRIPT => c"invalid script",
+        PI_BAD_SCRIPT_ID => c"unknown script id",
+        PI_BAD_SER_OFFSET => c"add serial data offset > 30 minute",
+        PI_GPIO_IN_USE => c"GPIO already in use",
+        PI_BAD_SERIAL_COUNT => c"must read at least a byte at a time",
+        PI_BAD_PARAM_NUM => c"script parameter id not 0-9",
+        PI_DUP_TAG => c"script has duplicate tag",
+        PI_TOO_MANY_TAGS => c"script has too many tags",
+        PI_BAD_SCRIPT_CMD => c"illegal script command",
+        PI_BAD_VAR_NUM => c"script variable id not 0-149",
+        PI_NO_SCRIPT_ROOM => c"no more room for scripts",
+        PI_NO_MEMORY => c"can't allocate temporary memory",
+        PI_SOCK_READ_FAILED => c"socket read failed",
+        PI_SOCK_WRIT_FAILED => c"socket write failed",
+        PI_TOO_MANY_PARAM => c"too many script parameters (> 10)",
+        PI_SCRIPT_NOT_READY => c"script initialising",
+        PI_BAD_TAG => c"script has unresolved tag",
+        PI_BAD_MICS_DELAY => c"bad MICS delay (too large)",
+        PI_BAD_MILS_DELAY => c"bad MILS delay (too large)",
+        PI_BAD_WAVE_ID => c"non existent wave id",
+        PI_TOO_MANY_CBS => c"No more CBs for waveform",
+        PI_TOO_MANY_OOL => c"No more OOL for waveform",
+        PI_EMPTY_WAVEFORM => c"attempt to create an empty waveform",
+        PI_NO_WAVEFORM_ID => c"no more waveform ids",
+        PI_I2C_OPEN_FAILED => c"can't open I2C device",
+        PI_SER_OPEN_FAILED => c"can't open serial device",
+        PI_SPI_OPEN_FAILED => c"can't open SPI device",
+        PI_BAD_I2C_BUS => c"bad I2C bus",
+        PI_BAD_I2C_ADDR => c"bad I2C address",
+        PI_BAD_SPI_CHANNEL => c"bad SPI channel",
+        PI_BAD_FLAGS => c"bad i2c/spi/ser open flags",
+        PI_BAD_SPI_SPEED => c"bad SPI speed",
+        PI_BAD_SER_DEVICE => c"bad serial device name",
+        PI_BAD_SER_SPEED => c"bad serial baud rate",
+        PI_BAD_PARAM => c"bad i2c/spi/ser parameter",
+        PI_I2C_WRITE_FAILED => c"I2C write failed",
+        PI_I2C_READ_FAILED => c"I2C read failed",
+        PI_BAD_SPI_COUNT => c"bad SPI count",
+        PI_SER_WRITE_FAILED => c"ser write failed",
+        PI_SER_READ_FAILED => c"ser read failed",
+        PI_SER_READ_NO_DATA => c"ser read no data available",
+        PI_UNKNOWN_COMMAND => c"unknown command",
+        PI_SPI_XFER_FAILED => c"spi xfer/read/write failed",
+        PI_BAD_POINTER => c"bad (NULL) pointer",
+        PI_NO_AUX_SPI => c"no auxiliary SPI on Pi A or B",
+        PI_NOT_PWM_GPIO => c"GPIO is not in use for PWM",
+        PI_NOT_SERVO_GPIO => c"GPIO is not in use for servo pulses",
+        PI_NOT_HCLK_GPIO => c"GPIO has no hardware clock",
+        PI_NOT_HPWM_GPIO => c"GPIO has no hardware PWM",
+        PI_BAD_HPWM_FREQ => c"invalid hardware PWM frequency",
+        PI_BAD_HPWM_DUTY => c"hardware PWM dutycycle not 0-1M",
+        PI_BAD_HCLK_FREQ => c"invalid hardware clock frequency",
+        PI_BAD_HCLK_PASS => c"need password to use hardware clock 1",
+        PI_HPWM_ILLEGAL => c"illegal, PWM in use for main clock",
+        PI_BAD_DATABITS => c"serial data bits not 1-32",
+        PI_BAD_STOPBITS => c"serial (half) stop bits not 2-8",
+        PI_MSG_TOOBIG => c"socket/pipe message too big",
+        PI_BAD_MALLOC_MODE => c"bad memory allocation mode",
+        PI_TOO_MANY_SEGS => c"too many I2C transaction segments",
+        PI_BAD_I2C_SEG => c"an I2C transaction segment failed",
+        PI_BAD_SMBUS_CMD => c"SMBus command not supported by driver",
+        PI_NOT_I2C_GPIO => c"no bit bang I2C in progress on GPIO",
+        PI_BAD_I2C_WLEN => c"bad I2C write length",
+        PI_BAD_I2C_RLEN => c"bad I2C read length",
+        PI_BAD_I2C_CMD => c"bad I2C command",
+        PI_BAD_I2C_BAUD => c"bad I2C baud rate, not 50-500k",
+        PI_CHAIN_LOOP_CNT => c"bad chain loop count",
+        PI_BAD_CHAIN_LOOP => c"empty chain loop",
+        PI_CHAIN_COUNTER => c"too many chain counters",
+        PI_BAD_CHAIN_CMD => c"bad chain command",
+        PI_BAD_CHAIN_DELAY => c"bad chain delay micros",
+        PI_CHAIN_NESTING => c"chain counters nested too deeply",
+        PI_CHAIN_TOO_BIG => c"chain is too long",
+        PI_DEPRECATED => c"deprecated function removed",
+        PI_BAD_SER_INVERT => c"bit bang serial invert not 0 or 1",
+        PI_BAD_EDGE => c"bad ISR edge value, not 0-2",
+        PI_BAD_ISR_INIT => c"bad ISR initialisation",
+        PI_BAD_FOREVER => c"loop forever must be last command",
+        PI_BAD_FILTER => c"bad filter parameter",
+        PI_BAD_PAD => c"bad pad number",
+        PI_BAD_STRENGTH => c"bad pad drive strength",
+        PI_FIL_OPEN_FAILED => c"file open failed",
+        PI_BAD_FILE_MODE => c"bad file mode",
+        PI_BAD_FILE_FLAG => c"bad file flag",
+        PI_BAD_FILE_READ => c"bad file read",
+        PI_BAD_FILE_WRITE => c"bad file write",
+        PI_FILE_NOT_ROPEN => c"file not open for read",
+        PI_FILE_NOT_WOPEN => c"file not open for write",
+        PI_BAD_FILE_SEEK => c"bad file seek",
+        PI_NO_FILE_MATCH => c"no files match pattern",
+        PI_NO_FILE_ACCESS => c"no permission to access file",
+        PI_FILE_IS_A_DIR => c"file is a directory",
+        PI_BAD_SHELL_STATUS => c"bad shell return status",
+        PI_BAD_SCRIPT_NAME => c"bad script name",
+        PI_BAD_SPI_BAUD => c"bad SPI baud rate, not 50-500k",
+        PI_NOT_SPI_GPIO => c"no bit bang SPI in progress on GPIO",
+        PI_BAD_EVENT_ID => c"bad event id",
+        PI_CMD_INTERRUPTED => c"command interrupted, Python",
+        PI_NOT_ON_BCM2711 => c"not available on BCM2711",
+        PI_ONLY_ON_BCM2711 => c"only available on BCM2711",
+        pigif_bad_send => c"failed to send to pigpiod",
+        pigif_bad_recv => c"failed to receive from pigpiod",
+        pigif_bad_getaddrinfo => c"failed to find address of pigpiod",
+        pigif_bad_connect => c"failed to connect to pigpiod",
+        pigif_bad_socket => c"failed to create socket",
+        pigif_bad_noib => c"failed to open notification in band",
+        pigif_duplicate_callback => c"identical callback exists",
+        pigif_bad_malloc => c"failed to malloc",
+        pigif_bad_callback => c"bad callback parameter",
+        pigif_notify_failed => c"failed to create notification thread",
+        pigif_callback_not_found => c"callback not found",
+        pigif_unconnected_pi => c"not connected to Pi",
+        pigif_too_many_pis => c"too many connected Pis",
+        _ => c"unknown error",
+    };
+    error.as_ptr()
+}
+
+pub unsafe fn set_mode(pi: ffi::c_int, gpio: ffi::c_uint, mode: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_MODES, gpio, mode, &[])
+}
+
+pub unsafe fn gpio_read(pi: ffi::c_int, gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_READ, gpio, 0, &[])
+}
+
+pub unsafe fn gpio_write(pi: ffi::c_int, gpio: ffi::c_uint, level: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_WRITE, gpio, level, &[])
+}
+
+pub unsafe fn set_pull_up_down(pi: ffi::c_int, gpio: ffi::c_uint, pud: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_PUD, gpio, pud, &[])
+}
+
+pub unsafe fn set_PWM_dutycycle(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    dutycycle: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_PWM, user_gpio, dutycycle, &[])
+}
+
+pub unsafe fn set_PWM_range(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    range: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_PRS, user_gpio, range, &[])
+}
+
+pub unsafe fn set_PWM_frequency(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    frequency: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_PFS, user_gpio, frequency, &[])
+}
+
+pub unsafe fn set_servo_pulsewidth(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    pulsewidth: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_SERVO, user_gpio, pulsewidth, &[])
+}
+
+pub unsafe fn hardware_PWM(
+    pi: ffi::c_int,
+    gpio: ffi::c_uint,
+    PWMfreq: ffi::c_uint,
+    PWMduty: u32,
+) -> ffi::c_int {
+    command(pi, PI_CMD_HP, gpio, PWMfreq, &PWMduty.to_le_bytes())
+}
+
+pub unsafe fn wave_clear(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVCLR, 0, 0, &[])
+}
+
+pub unsafe fn wave_add_new(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVNEW, 0, 0, &[])
+}
+
+pub unsafe fn wave_add_generic(
+    pi: ffi::c_int,
+    numPulses: ffi::c_uint,
+    pulses: *mut gpioPulse_t,
+) -> ffi::c_int {
+    if numPulses == 0 {
+        return 0;
+    }
+
+    let pulses = slice::from_raw_parts(pulses, numPulses as usize);
+    let ext = pulses
+        .iter()
+        .flat_map(|pulse| [pulse.gpioOn, pulse.gpioOff, pulse.usDelay])
+        .flat_map(u32::to_le_bytes)
+        .collect::<Vec<_>>();
+    command(pi, PI_CMD_WVAG, 0, 0, &ext)
+}
+
+pub unsafe fn wave_create(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVCRE, 0, 0, &[])
+}
+
+pub unsafe fn wave_delete(pi: ffi::c_int, wave_id: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_WVDEL, wave_id, 0, &[])
+}
+
+pub unsafe fn wave_send_once(pi: ffi::c_int, wave_id: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_WVTX, wave_id, 0, &[])
+}
+
+pub unsafe fn wave_tx_busy(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVBSY, 0, 0, &[])
+}
+
+pub unsafe fn callback_ex(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    edge: ffi::c_uint,
+    f: CBFuncEx_t,
+    userdata: *mut ffi::c_void,
+) -> ffi::c_int {
+    if user_gpio > PI_MAX_USER_GPIO {
+        return PI_BAD_USER_GPIO;
+    }
+    if edge > EITHER_EDGE {
+        return PI_BAD_EDGE;
+    }
+    if f.is_none() {
+        return pigif_bad_callback;
+    }
+
+    register(pi, |monitor, id| {
+        monitor.callbacks.push(Callback {
+            id,
+            gpio: user_gpio,
+            edge,
+            f,
+            userdata: Userdata(userdata),
+        });
+    })
+}
+
+pub unsafe fn callback_cancel(callback_id: ffi::c_uint) -> ffi::c_int {
+    unregister(|monitor| {
+        let len = monitor.callbacks.len();
+        monitor
+            .callbacks
+            .retain(|callback| callback.id != callback_id);
+        monitor.callbacks.len() != len
+    })
+}
+
+unsafe extern "C" fn edge_seen(
+    _pi: ffi::c_int,
+    _user_gpio: ffi::c_uint,
+    _level: ffi::c_uint,
+    _tick: u32,
+    userdata: *mut ffi::c_void,
+) {
+    (*userdata.cast::<atomic::AtomicBool>()).store(true, atomic::Ordering::Relaxed);
+}
+
+pub unsafe fn wait_for_edge(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    edge: ffi::c_uint,
+    timeout: f64,
+) -> ffi::c_int {
+    let seen = atomic::AtomicBool::new(false);
+
+    let id = callback_ex(
+        pi,
+        user_gpio,
+        edge,
+        Some(edge_seen),
+        (&seen as *const atomic::AtomicBool).cast_mut().cast(),
+    );
+    if id.is_negative() {
+        return id;
+    }
+
+    wait_until_seen(&seen, timeout);
+
+    // once cancelled `seen` is not touched anymore
+    callback_cancel(id as ffi::c_uint);
+    seen.load(atomic::Ordering::Relaxed) as ffi::c_int
+}
+
+fn wait_until_seen(seen: &atomic::AtomicBool, timeout: f64) {
+    // no deadline if the timeout doesn't fit
+    let deadline = time::Duration::try_from_secs_f64(timeout.max(0.))
+        .ok()
+        .and_then(|timeout| time::Instant::now().checked_add(timeout));
+
+    // polls like the C library does
+    while !seen.load(atomic::Ordering::Relaxed)
+        && deadline.map_or(true, |deadline| time::Instant::now() < deadline)
+    {
+        thread::sleep(time::Duration::from_millis(50));
+    }
+}
+
+pub unsafe fn event_callback_ex(
+    pi: ffi::c_int,
+    event: ffi::c_uint,
+    f: evtCBFuncEx_t,
+    userdata: *mut ffi::c_void,
+) -> ffi::c_int {
+    if event > PI_MAX_EVENT {
+        return PI_BAD_EVENT_ID;
+    }
+    if f.is_none() {
+        return pigif_bad_callback;
+    }
+
+    register(pi, |monitor, id| {
+        monitor.events.push(EventCallback {
+            id,
+            event,
+            f,
+            userdata: Userdata(userdata),
+        });
+    })
+}
+
+pub unsafe fn event_callback_cancel(callback_id: ffi::c_uint) -> ffi::c_int {
+    unregister(|monitor| {
+        let len = monitor.events.len();
+        monitor.events.retain(|callback| callback.id != callback_id);
+        monitor.events.len() != len
+    })
+}
+
+unsafe extern "C" fn event_seen(
+    _pi: ffi::c_int,
+    _event: ffi::c_uint,
+    _tick: u32,
+    userdata: *mut ffi::c_void,
+) {
+    (*userdata.cast::<atomic::AtomicBool>()).store(true, atomic::Ordering::Relaxed);
+}
+
+pub unsafe fn wait_for_event(pi: ffi::c_int, event: ffi::c_uint, timeout: f64) -> ffi::c_int {
+    let seen = atomic::AtomicBool::new(false);
+
+    let id = event_callback_ex(
+        pi,
+        event,
+        Some(event_seen),
+        (&seen as *const atomic::AtomicBool).cast_mut().cast(),
+    );
+    if id.is_negative() {
+        return id;
+    }
+
+    wait_until_seen(&seen, timeout);
+
+    // once cancelled `seen` is not touched anymore
+    event_callback_cancel(id as ffi::c_uint);
+    seen.load(atomic::Ordering::Relaxed) as ffi::c_int
+}
+
+pub unsafe fn event_trigger(pi: ffi::c_int, event: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_EVT, event, 0, &[])
+}
+
+pub unsafe fn notify_open(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_NO, 0, 0, &[])
+}
+
+pub unsafe fn notify_begin(pi: ffi::c_int, handle: ffi::c_uint, bits: u32) -> ffi::c_int {
+    command(pi, PI_CMD_NB, handle, bits, &[])
+}
+
+pub unsafe fn notify_pause(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_NP, handle, 0, &[])
+}
+
+pub unsafe fn notify_close(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_NC, handle, 0, &[])
+}
+
+pub unsafe fn store_script(pi: ffi::c_int, script: *mut ffi::c_char) -> ffi::c_int {
+    let script = ffi::CStr::from_ptr(script).to_bytes();
+    command(pi, PI_CMD_PROC, 0, 0, script)
+}
+
+unsafe fn params(numPar: ffi::c_uint, param: *mut u32) -> Vec<u8> {
+    if param.is_null() {
+        return Vec::new();
+    }
+
+    slice::from_raw_parts(param, numPar as usize)
+        .iter()
+        .flat_map(|param| param.to_le_bytes())
+        .collect()
+}
+
+pub unsafe fn run_script(
+    pi: ffi::c_int,
+    script_id: ffi::c_uint,
+    numPar: ffi::c_uint,
+    param: *mut u32,
+) -> ffi::c_int {
+    command(pi, PI_CMD_PROCR, script_id, 0, &params(numPar, param))
+}
+
+pub unsafe fn update_script(
+    pi: ffi::c_int,
+    script_id: ffi::c_uint,
+    numPar: ffi::c_uint,
+    param: *mut u32,
+) -> ffi::c_int {
+    command(pi, PI_CMD_PROCU, script_id, 0, &params(numPar, param))
+}
+
+pub unsafe fn script_status(pi: ffi::c_int, script_id: ffi::c_uint, param: *mut u32) -> ffi::c_int {
+    // the status followed by every parameter
+    let mut buf = [0u8; 4 * (PI_MAX_SCRIPT_PARAMS as usize + 1)];
+    let count = command_recv(pi, PI_CMD_PROCP, script_id, 0, &[], &mut buf);
+    if count <= 0 {
+        return count;
+    }
+
+    let mut words = buf
+        .chunks_exact(4)
+        .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]));
+    let status = words.next().unwrap_or(PI_SCRIPT_FAILED);
+    if !param.is_null() {
+        let param = slice::from_raw_parts_mut(param, PI_MAX_SCRIPT_PARAMS as usize);
+        for (param, word) in param.iter_mut().zip(words) {
+            *param = word;
+        }
+    }
+    status as ffi::c_int
+}
+
+pub unsafe fn stop_script(pi: ffi::c_int, script_id: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_PROCS, script_id, 0, &[])
+}
+
+pub unsafe fn delete_script(pi: ffi::c_int, script_id: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_PROCD, script_id, 0, &[])
+}
+
+pub unsafe fn shell_(
+    pi: ffi::c_int,
+    scriptName: *mut ffi::c_char,
+    scriptString: *mut ffi::c_char,
+) -> ffi::c_int {
+    // the name keeps its terminating nul to separate it from the arguments
+    let name = ffi::CStr::from_ptr(scriptName).to_bytes_with_nul();
+    let string = ffi::CStr::from_ptr(scriptString).to_bytes();
+    let ext = [name, string].concat();
+    command(pi, PI_CMD_SHELL, name.len() as u32 - 1, 0, &ext)
+}
+
+pub unsafe fn file_open(pi: ffi::c_int, file: *mut ffi::c_char, mode: ffi::c_uint) -> ffi::c_int {
+    let file = ffi::CStr::from_ptr(file).to_bytes();
+    command(pi, PI_CMD_FO, mode, 0, file)
+}
+
+pub unsafe fn file_close(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_FC, handle, 0, &[])
+}
+
+pub unsafe fn file_read(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), count as usize);
+    command_recv(pi, PI_CMD_FR, handle, count, &[], buf)
+}
+
+pub unsafe fn file_write(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts(buf.cast::<u8>(), count as usize);
+    command(pi, PI_CMD_FW, handle, 0, buf)
+}
+
+pub unsafe fn file_seek(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    seekOffset: i32,
+    seekFrom: ffi::c_int,
+) -> ffi::c_int {
+    let from = (seekFrom as u32).to_le_bytes();
+    command(pi, PI_CMD_FS, handle, seekOffset as u32, &from)
+}
+
+pub unsafe fn file_list(
+    pi: ffi::c_int,
+    fpat: *mut ffi::c_char,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let fpat = ffi::CStr::from_ptr(fpat).to_bytes();
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), count as usize);
+    // the C library always asks for this many bytes
+    command_recv(pi, PI_CMD_FL, 60000, 0, fpat, buf)
+}
+
+pub unsafe fn i2c_open(
+    pi: ffi::c_int,
+    i2c_bus: ffi::c_uint,
+    i2c_addr: ffi::c_uint,
+    i2c_flags: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_I2CO, i2c_bus, i2c_addr, &i2c_flags.to_le_bytes())
+}
+
+pub unsafe fn i2c_close(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_I2CC, handle, 0, &[])
+}
+
+pub unsafe fn i2c_write_byte(pi: ffi::c_int, handle: ffi::c_uint, bVal: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_I2CWS, handle, bVal, &[])
+}
+
+pub unsafe fn i2c_read_byte(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_I2CRS, handle, 0, &[])
+}
+
+pub unsafe fn i2c_write_byte_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    bVal: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_I2CWB, handle, i2c_reg, &bVal.to_le_bytes())
+}
+
+pub unsafe fn i2c_write_word_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    wVal: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_I2CWW, handle, i2c_reg, &wVal.to_le_bytes())
+}
+
+pub unsafe fn i2c_read_byte_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_I2CRB, handle, i2c_reg, &[])
+}
+
+pub unsafe fn i2c_read_word_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_I2CRW, handle, i2c_reg, &[])
+}
+
+pub unsafe fn i2c_write_block_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts(buf.cast::<u8>(), count as usize);
+    command(pi, PI_CMD_I2CWK, handle, i2c_reg, buf)
+}
+
+pub unsafe fn i2c_read_block_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    buf: *mut ffi::c_char,
+) -> ffi::c_int {
+    // smbus blocks are at most 32 bytes
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), 32);
+    command_recv(pi, PI_CMD_I2CRK, handle, i2c_reg, &[], buf)
+}
+
+pub unsafe fn i2c_read_i2c_block_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), count as usize);
+    command_recv(pi, PI_CMD_I2CRI, handle, i2c_reg, &count.to_le_bytes(), buf)
+}
+
+pub unsafe fn i2c_write_i2c_block_data(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts(buf.cast::<u8>(), count as usize);
+    command(pi, PI_CMD_I2CWI, handle, i2c_reg, buf)
+}
+
+pub unsafe fn i2c_read_device(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), count as usize);
+    command_recv(pi, PI_CMD_I2CRD, handle, count, &[], buf)
+}
+
+pub unsafe fn i2c_write_device(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts(buf.cast::<u8>(), count as usize);
+    command(pi, PI_CMD_I2CWD, handle, 0, buf)
+}
+
+pub unsafe fn i2c_zip(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    inBuf: *mut ffi::c_char,
+    inLen: ffi::c_uint,
+    outBuf: *mut ffi::c_char,
+    outLen: ffi::c_uint,
+) -> ffi::c_int {
+    let in_buf = slice::from_raw_parts(inBuf.cast::<u8>(), inLen as usize);
+    let out_buf = slice::from_raw_parts_mut(outBuf.cast::<u8>(), outLen as usize);
+    command_recv(pi, PI_CMD_I2CZ, handle, 0, in_buf, out_buf)
+}
+
+pub unsafe fn spi_open(
+    pi: ffi::c_int,
+    spi_channel: ffi::c_uint,
+    baud: ffi::c_uint,
+    spi_flags: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_SPIO, spi_channel, baud, &spi_flags.to_le_bytes())
+}
+
+pub unsafe fn spi_close(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_SPIC, handle, 0, &[])
+}
+
+pub unsafe fn spi_read(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), count as usize);
+    command_recv(pi, PI_CMD_SPIR, handle, count, &[], buf)
+}
+
+pub unsafe fn spi_write(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts(buf.cast::<u8>(), count as usize);
+    command(pi, PI_CMD_SPIW, handle, 0, buf)
+}
+
+pub unsafe fn spi_xfer(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    txBuf: *mut ffi::c_char,
+    rxBuf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let tx_buf = slice::from_raw_parts(txBuf.cast::<u8>(), count as usize);
+    let rx_buf = slice::from_raw_parts_mut(rxBuf.cast::<u8>(), count as usize);
+    command_recv(pi, PI_CMD_SPIX, handle, 0, tx_buf, rx_buf)
+}
+
+pub unsafe fn serial_open(
+    pi: ffi::c_int,
+    ser_tty: *mut ffi::c_char,
+    baud: ffi::c_uint,
+    ser_flags: ffi::c_uint,
+) -> ffi::c_int {
+    let ser_tty = ffi::CStr::from_ptr(ser_tty).to_bytes();
+    command(pi, PI_CMD_SERO, baud, ser_flags, ser_tty)
+}
+
+pub unsafe fn serial_close(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_SERC, handle, 0, &[])
+}
+
+pub unsafe fn serial_read(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), count as usize);
+    command_recv(pi, PI_CMD_SERR, handle, count, &[], buf)
+}
+
+pub unsafe fn serial_write(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts(buf.cast::<u8>(), count as usize);
+    command(pi, PI_CMD_SERW, handle, 0, buf)
+}
+
+pub unsafe fn serial_data_available(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_SERDA, handle, 0, &[])
+}
+
+pub unsafe fn set_bank_1(pi: ffi::c_int, bits: u32) -> ffi::c_int {
+    command(pi, PI_CMD_BS1, bits, 0, &[])
+}
+
+pub unsafe fn clear_bank_1(pi: ffi::c_int, bits: u32) -> ffi::c_int {
+    command(pi, PI_CMD_BC1, bits, 0, &[])
+}
+
+pub unsafe fn time_time() -> f64 {
+    time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .map_or(0., |since| since.as_secs_f64())
+}
+
+pub unsafe fn time_sleep(seconds: f64) {
+    if let Ok(duration) = time::Duration::try_from_secs_f64(seconds) {
+        thread::sleep(duration);
+    }
+}
+
+pub unsafe fn hardware_clock(
+    pi: ffi::c_int,
+    gpio: ffi::c_uint,
+    clkfreq: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_HC, gpio, clkfreq, &[])
+}
+
+pub unsafe fn gpio_trigger(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    pulseLen: ffi::c_uint,
+    level: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_TRIG, user_gpio, pulseLen, &level.to_le_bytes())
+}
+
+pub unsafe fn pigpiod_if_version() -> ffi::c_uint {
+    PIGPIOD_IF2_VERSION
+}
+
+pub unsafe fn get_mode(pi: ffi::c_int, gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_MODEG, gpio, 0, &[])
+}
+
+pub unsafe fn set_watchdog(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    timeout: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_WDOG, user_gpio, timeout, &[])
+}
+
+pub unsafe fn set_glitch_filter(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    steady: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_FG, user_gpio, steady, &[])
+}
+
+pub unsafe fn set_noise_filter(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    steady: ffi::c_uint,
+    active: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_FN, user_gpio, steady, &active.to_le_bytes())
+}
+
+pub unsafe fn get_hardware_revision(pi: ffi::c_int) -> u32 {
+    command(pi, PI_CMD_HWVER, 0, 0, &[]) as u32
+}
+
+pub unsafe fn get_pigpio_version(pi: ffi::c_int) -> u32 {
+    command(pi, PI_CMD_PIGPV, 0, 0, &[]) as u32
+}
+
+pub unsafe fn get_pad_strength(pi: ffi::c_int, pad: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_PADG, pad, 0, &[])
+}
+
+pub unsafe fn set_pad_strength(
+    pi: ffi::c_int,
+    pad: ffi::c_uint,
+    padStrength: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_PADS, pad, padStrength, &[])
+}
+
+pub unsafe fn custom_1(
+    pi: ffi::c_int,
+    arg1: ffi::c_uint,
+    arg2: ffi::c_uint,
+    argx: *mut ffi::c_char,
+    argc: ffi::c_uint,
+) -> ffi::c_int {
+    let argx = slice::from_raw_parts(argx.cast::<u8>(), argc as usize);
+    command(pi, PI_CMD_CF1, arg1, arg2, argx)
+}
+
+pub unsafe fn custom_2(
+    pi: ffi::c_int,
+    arg1: ffi::c_uint,
+    argx: *mut ffi::c_char,
+    argc: ffi::c_uint,
+    retBuf: *mut ffi::c_char,
+    retMax: ffi::c_uint,
+) -> ffi::c_int {
+    let argx = slice::from_raw_parts(argx.cast::<u8>(), argc as usize);
+    let ret_buf = slice::from_raw_parts_mut(retBuf.cast::<u8>(), retMax as usize);
+    command_recv(pi, PI_CMD_CF2, arg1, retMax, argx, ret_buf)
+}
+
+pub unsafe fn set_bank_2(pi: ffi::c_int, bits: u32) -> ffi::c_int {
+    command(pi, PI_CMD_BS2, bits, 0, &[])
+}
+
+pub unsafe fn clear_bank_2(pi: ffi::c_int, bits: u32) -> ffi::c_int {
+    command(pi, PI_CMD_BC2, bits, 0, &[])
+}
+
+pub unsafe fn get_PWM_dutycycle(pi: ffi::c_int, user_gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_GDC, user_gpio, 0, &[])
+}
+
+pub unsafe fn get_PWM_range(pi: ffi::c_int, user_gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_PRG, user_gpio, 0, &[])
+}
+
+pub unsafe fn get_PWM_real_range(pi: ffi::c_int, user_gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_PRRG, user_gpio, 0, &[])
+}
+
+pub unsafe fn get_PWM_frequency(pi: ffi::c_int, user_gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_PFG, user_gpio, 0, &[])
+}
+
+pub unsafe fn get_servo_pulsewidth(pi: ffi::c_int, user_gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_GPW, user_gpio, 0, &[])
+}
+
+// same arguments as the C library
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn wave_add_serial(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    baud: ffi::c_uint,
+    data_bits: ffi::c_uint,
+    stop_bits: ffi::c_uint,
+    offset: ffi::c_uint,
+    numBytes: ffi::c_uint,
+    str_: *mut ffi::c_char,
+) -> ffi::c_int {
+    if numBytes == 0 {
+        return 0;
+    }
+
+    let bytes = slice::from_raw_parts(str_.cast::<u8>(), numBytes as usize);
+    let ext = [data_bits, stop_bits, offset]
+        .into_iter()
+        .flat_map(u32::to_le_bytes)
+        .chain(bytes.iter().copied())
+        .collect::<Vec<_>>();
+    command(pi, PI_CMD_WVAS, user_gpio, baud, &ext)
+}
+
+pub unsafe fn wave_create_and_pad(pi: ffi::c_int, percent: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVCAP, percent as u32, 0, &[])
+}
+
+pub unsafe fn wave_send_repeat(pi: ffi::c_int, wave_id: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_WVTXR, wave_id, 0, &[])
+}
+
+pub unsafe fn wave_send_using_mode(
+    pi: ffi::c_int,
+    wave_id: ffi::c_uint,
+    mode: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_WVTXM, wave_id, mode, &[])
+}
+
+pub unsafe fn wave_chain(
+    pi: ffi::c_int,
+    buf: *mut ffi::c_char,
+    bufSize: ffi::c_uint,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts(buf.cast::<u8>(), bufSize as usize);
+    command(pi, PI_CMD_WVCHA, 0, 0, buf)
+}
+
+pub unsafe fn wave_tx_at(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVTAT, 0, 0, &[])
+}
+
+pub unsafe fn wave_tx_stop(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVHLT, 0, 0, &[])
+}
+
+// the statistics commands take 0 for the current, 1 for the highest and 2 for
+// the maximum figure
+pub unsafe fn wave_get_micros(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSM, 0, 0, &[])
+}
+
+pub unsafe fn wave_get_high_micros(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSM, 1, 0, &[])
+}
+
+pub unsafe fn wave_get_max_micros(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSM, 2, 0, &[])
+}
+
+pub unsafe fn wave_get_pulses(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSP, 0, 0, &[])
+}
+
+pub unsafe fn wave_get_high_pulses(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSP, 1, 0, &[])
+}
+
+pub unsafe fn wave_get_max_pulses(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSP, 2, 0, &[])
+}
+
+pub unsafe fn wave_get_cbs(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSC, 0, 0, &[])
+}
+
+pub unsafe fn wave_get_high_cbs(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSC, 1, 0, &[])
+}
+
+pub unsafe fn wave_get_max_cbs(pi: ffi::c_int) -> ffi::c_int {
+    command(pi, PI_CMD_WVSC, 2, 0, &[])
+}
+
+pub unsafe fn i2c_write_quick(pi: ffi::c_int, handle: ffi::c_uint, bit: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_I2CWQ, handle, bit, &[])
+}
+
+pub unsafe fn i2c_process_call(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    wVal: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_I2CPC, handle, i2c_reg, &wVal.to_le_bytes())
+}
+
+pub unsafe fn i2c_block_process_call(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    i2c_reg: ffi::c_uint,
+    buf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let tx = slice::from_raw_parts(buf.cast::<u8>(), count as usize).to_vec();
+    // the answer replaces what was sent, smbus blocks are at most 32 bytes
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), 32);
+    command_recv(pi, PI_CMD_I2CPK, handle, i2c_reg, &tx, buf)
+}
+
+pub unsafe fn serial_read_byte(pi: ffi::c_int, handle: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_SERRB, handle, 0, &[])
+}
+
+pub unsafe fn serial_write_byte(
+    pi: ffi::c_int,
+    handle: ffi::c_uint,
+    bVal: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_SERWB, handle, bVal, &[])
+}
+
+pub unsafe fn bb_i2c_open(
+    pi: ffi::c_int,
+    SDA: ffi::c_uint,
+    SCL: ffi::c_uint,
+    baud: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_BI2CO, SDA, SCL, &baud.to_le_bytes())
+}
+
+pub unsafe fn bb_i2c_close(pi: ffi::c_int, SDA: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_BI2CC, SDA, 0, &[])
+}
+
+pub unsafe fn bb_i2c_zip(
+    pi: ffi::c_int,
+    SDA: ffi::c_uint,
+    inBuf: *mut ffi::c_char,
+    inLen: ffi::c_uint,
+    outBuf: *mut ffi::c_char,
+    outLen: ffi::c_uint,
+) -> ffi::c_int {
+    let in_buf = slice::from_raw_parts(inBuf.cast::<u8>(), inLen as usize);
+    let out_buf = slice::from_raw_parts_mut(outBuf.cast::<u8>(), outLen as usize);
+    command_recv(pi, PI_CMD_BI2CZ, SDA, 0, in_buf, out_buf)
+}
+
+pub unsafe fn bb_spi_open(
+    pi: ffi::c_int,
+    CS: ffi::c_uint,
+    MISO: ffi::c_uint,
+    MOSI: ffi::c_uint,
+    SCLK: ffi::c_uint,
+    baud: ffi::c_uint,
+    spi_flags: ffi::c_uint,
+) -> ffi::c_int {
+    let ext = [MISO, MOSI, SCLK, baud, spi_flags]
+        .into_iter()
+        .flat_map(u32::to_le_bytes)
+        .collect::<Vec<_>>();
+    command(pi, PI_CMD_BSPIO, CS, 0, &ext)
+}
+
+pub unsafe fn bb_spi_close(pi: ffi::c_int, CS: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_BSPIC, CS, 0, &[])
+}
+
+pub unsafe fn bb_spi_xfer(
+    pi: ffi::c_int,
+    CS: ffi::c_uint,
+    txBuf: *mut ffi::c_char,
+    rxBuf: *mut ffi::c_char,
+    count: ffi::c_uint,
+) -> ffi::c_int {
+    let tx_buf = slice::from_raw_parts(txBuf.cast::<u8>(), count as usize);
+    let rx_buf = slice::from_raw_parts_mut(rxBuf.cast::<u8>(), count as usize);
+    command_recv(pi, PI_CMD_BSPIX, CS, 0, tx_buf, rx_buf)
+}
+
+pub unsafe fn bb_serial_read_open(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    baud: ffi::c_uint,
+    data_bits: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_SLRO, user_gpio, baud, &data_bits.to_le_bytes())
+}
+
+pub unsafe fn bb_serial_read(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    buf: *mut ffi::c_void,
+    bufSize: usize,
+) -> ffi::c_int {
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), bufSize);
+    command_recv(pi, PI_CMD_SLR, user_gpio, bufSize as u32, &[], buf)
+}
+
+pub unsafe fn bb_serial_read_close(pi: ffi::c_int, user_gpio: ffi::c_uint) -> ffi::c_int {
+    command(pi, PI_CMD_SLRC, user_gpio, 0, &[])
+}
+
+pub unsafe fn bb_serial_invert(
+    pi: ffi::c_int,
+    user_gpio: ffi::c_uint,
+    invert: ffi::c_uint,
+) -> ffi::c_int {
+    command(pi, PI_CMD_SLRI, user_gpio, invert, &[])
+}
+
+pub unsafe fn bsc_xfer(pi: ffi::c_int, bscxfer: *mut bsc_xfer_t) -> ffi::c_int {
+    let xfer = &mut *bscxfer;
+    let tx_len = usize::try_from(xfer.txCnt)
+        .unwrap_or(0)
+        .min(xfer.txBuf.len());
+    let tx = slice::from_raw_parts(xfer.txBuf.as_ptr().cast::<u8>(), tx_len);
+
+    // the status word followed by what the master wrote
+    let mut buf = [0u8; 4 + 512];
+    let count = command_recv(pi, PI_CMD_BSCX, xfer.control, 0, tx, &mut buf);
+    if count < 4 {
+        xfer.rxCnt = 0;
+        return count;
+    }
+
+    let rx = &buf[4..count as usize];
+    for (dst, src) in xfer.rxBuf.iter_mut().zip(rx) {
+        *dst = *src as ffi::c_char;
+    }
+    xfer.rxCnt = rx.len() as ffi::c_int;
+    u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as ffi::c_int
+}
+
+pub unsafe fn bsc_i2c(
+    pi: ffi::c_int,
+    i2c_addr: ffi::c_int,
+    bscxfer: *mut bsc_xfer_t,
+) -> ffi::c_int {
+    // same control word the C library uses, 0 closes the slave
+    (*bscxfer).control = if i2c_addr != 0 {
+        (i2c_addr as u32) << 16 | 0x305
+    } else {
+        0
+    };
+    bsc_xfer(pi, bscxfer)
+}
@@ -0,0 +1,92 @@
+use std::ffi;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Handle, Init, Pi, PiConnection, Result};
+
+// closed when dropped
+#[derive(Debug)]
+pub struct Spi<C: PiConnection = Pi<Init>> {
+    pi: C,
+    handle: Handle,
+}
+
+impl<C: PiConnection> Spi<C> {
+    // `flags` is pigpio's spiFlags word, 0 is mode 0 on the main SPI with active low CE
+    pub fn open(
+        pi: C,
+        channel: ffi::c_uint,
+        baud: ffi::c_uint,
+        flags: ffi::c_uint,
+    ) -> Result<Self> {
+        let handle = unsafe { pigpiod_if2::spi_open(pi.pi().0 .0, channel, baud, flags) };
+
+        if handle.is_negative() {
+            return Err(Error::new(handle));
+        }
+
+        Ok(Self {
+            pi,
+            handle: Handle(handle as ffi::c_uint),
+        })
+    }
+
+    pub fn close(self) {}
+
+    pub(crate) fn pi(&self) -> C::Guard<'_> {
+        self.pi.pi()
+    }
+
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let pbuf = buf.as_mut_ptr().cast();
+        let buflen = buf.len() as ffi::c_uint;
+
+        let count =
+            unsafe { pigpiod_if2::spi_read(self.pi.pi().0 .0, self.handle.0, pbuf, buflen) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn write(&self, bytes: &[u8]) -> Result<usize> {
+        let pbuf = bytes.as_ptr().cast_mut().cast();
+        let buflen = bytes.len() as ffi::c_uint;
+
+        let count =
+            unsafe { pigpiod_if2::spi_write(self.pi.pi().0 .0, self.handle.0, pbuf, buflen) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    // full duplex, `rx` has to be as long as `tx`
+    pub fn xfer(&self, tx: &[u8], rx: &mut [u8]) -> Result<usize> {
+        if tx.len() != rx.len() {
+            return Err(Error::Pi(ErrorCode::BadSpiCount));
+        }
+
+        let ptx = tx.as_ptr().cast_mut().cast();
+        let prx = rx.as_mut_ptr().cast();
+        let buflen = tx.len() as ffi::c_uint;
+
+        let count =
+            unsafe { pigpiod_if2::spi_xfer(self.pi.pi().0 .0, self.handle.0, ptx, prx, buflen) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+}
+
+impl<C: PiConnection> Drop for Spi<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::spi_close(self.pi.pi().0 .0, self.handle.0) };
+    }
+}
@@ -0,0 +1,101 @@
+use std::ffi;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Init, Pi, Result};
+
+// version of the client library, not of the daemon
+pub fn pigpiod_if_version() -> ffi::c_uint {
+    unsafe { pigpiod_if2::pigpiod_if_version() }
+}
+
+// groups of gpios sharing a drive strength, 0-27, 28-45 and 46-53
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pad(ffi::c_uint);
+
+impl Pad {
+    pub fn new(pad: ffi::c_uint) -> Option<Self> {
+        if pad > pigpiod_if2::PI_MAX_PAD {
+            return None;
+        }
+
+        Some(Pad(pad))
+    }
+
+    pub fn get(self) -> ffi::c_uint {
+        self.0
+    }
+}
+
+impl Pi<Init> {
+    // the revision code from /proc/cpuinfo, 0 if it couldn't be read
+    pub fn hardware_revision(&self) -> u32 {
+        unsafe { pigpiod_if2::get_hardware_revision(self.0 .0) }
+    }
+
+    pub fn pigpio_version(&self) -> u32 {
+        unsafe { pigpiod_if2::get_pigpio_version(self.0 .0) }
+    }
+
+    // in mA
+    pub fn get_pad_strength(&self, pad: Pad) -> Result<ffi::c_uint> {
+        let strength = unsafe { pigpiod_if2::get_pad_strength(self.0 .0, pad.0) };
+
+        if strength.is_negative() {
+            return Err(Error::new(strength));
+        }
+
+        Ok(strength as ffi::c_uint)
+    }
+
+    // in mA, 1-16
+    pub fn set_pad_strength(&self, pad: Pad, strength: ffi::c_uint) -> Result<()> {
+        if !(pigpiod_if2::PI_MIN_PAD_STRENGTH..=pigpiod_if2::PI_MAX_PAD_STRENGTH)
+            .contains(&strength)
+        {
+            return Err(Error::Pi(ErrorCode::BadStrength));
+        }
+
+        let err = unsafe { pigpiod_if2::set_pad_strength(self.0 .0, pad.0, strength) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // calls the daemon's gpioCustom1, which does nothing unless pigpio was patched
+    pub fn custom_1(
+        &self,
+        arg1: ffi::c_uint,
+        arg2: ffi::c_uint,
+        argx: &[u8],
+    ) -> Result<ffi::c_int> {
+        let pargx = argx.as_ptr().cast_mut().cast();
+        let argc = argx.len() as ffi::c_uint;
+
+        let res = unsafe { pigpiod_if2::custom_1(self.0 .0, arg1, arg2, pargx, argc) };
+
+        if res.is_negative() {
+            return Err(Error::new(res));
+        }
+
+        Ok(res)
+    }
+
+    // calls the daemon's gpioCustom2, returns how much of `buf` it filled
+    pub fn custom_2(&self, arg1: ffi::c_uint, argx: &[u8], buf: &mut [u8]) -> Result<usize> {
+        let pargx = argx.as_ptr().cast_mut().cast();
+        let argc = argx.len() as ffi::c_uint;
+        let pbuf = buf.as_mut_ptr().cast();
+        let buflen = buf.len() as ffi::c_uint;
+
+        let count = unsafe { pigpiod_if2::custom_2(self.0 .0, arg1, pargx, argc, pbuf, buflen) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+}
@@ -0,0 +1,378 @@
+use std::ffi;
+use std::thread;
+use std::time;
+
+use super::pigpiod_if2;
+use super::{Error, ErrorCode, Gpio, GpioLevel, Init, Pi, PiConnection, Result};
+
+// levels to switch at once, followed by a delay in microseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Pulse {
+    on: u32,
+    off: u32,
+    delay: u32,
+}
+
+impl Pulse {
+    pub fn new(delay: u32) -> Self {
+        Pulse {
+            on: 0,
+            off: 0,
+            delay,
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn from_bits(on: u32, off: u32, delay: u32) -> Self {
+        Pulse { on, off, delay }
+    }
+
+    // waves can only drive the user gpios 0-31
+    pub fn set(self, gpio: Gpio, level: GpioLevel) -> Result<Self> {
+        if gpio.0 > pigpiod_if2::PI_MAX_USER_GPIO {
+            return Err(Error::Pi(ErrorCode::BadUserGpio));
+        }
+
+        let bit = 1 << gpio.0;
+        let pulse = match level {
+            GpioLevel::Low => Pulse {
+                on: self.on & !bit,
+                off: self.off | bit,
+                ..self
+            },
+            GpioLevel::High => Pulse {
+                on: self.on | bit,
+                off: self.off & !bit,
+                ..self
+            },
+        };
+
+        Ok(pulse)
+    }
+
+    pub fn levels(&self) -> impl Iterator<Item = (Gpio, GpioLevel)> + '_ {
+        (0..=pigpiod_if2::PI_MAX_USER_GPIO).filter_map(|gpio| {
+            let level = if self.on >> gpio & 1 == 1 {
+                GpioLevel::High
+            } else if self.off >> gpio & 1 == 1 {
+                GpioLevel::Low
+            } else {
+                return None;
+            };
+            Some((Gpio(gpio), level))
+        })
+    }
+
+    pub fn delay(&self) -> time::Duration {
+        time::Duration::from_micros(self.delay.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaveMode {
+    OneShot = pigpiod_if2::PI_WAVE_MODE_ONE_SHOT as isize,
+    Repeat = pigpiod_if2::PI_WAVE_MODE_REPEAT as isize,
+    // wait for the current wave to reach its end before starting
+    OneShotSync = pigpiod_if2::PI_WAVE_MODE_ONE_SHOT_SYNC as isize,
+    RepeatSync = pigpiod_if2::PI_WAVE_MODE_REPEAT_SYNC as isize,
+}
+
+// which figure the wave statistics report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaveStat {
+    // of the wave being built
+    Current,
+    // highest since the daemon started
+    High,
+    // what the daemon allows
+    Max,
+}
+
+// deleted when dropped
+#[derive(Debug)]
+pub struct Wave<C: PiConnection = Pi<Init>> {
+    pi: C,
+    id: ffi::c_uint,
+}
+
+impl Pi<Init> {
+    // throws away every wave, including the ones that are still referenced
+    pub fn wave_clear(&self) -> Result<()> {
+        let err = unsafe { pigpiod_if2::wave_clear(self.0 .0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // starts over with an empty wave, waves created before are kept
+    pub fn wave_add_new(&self) -> Result<()> {
+        let err = unsafe { pigpiod_if2::wave_add_new(self.0 .0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // returns the number of pulses in the wave so far
+    pub fn wave_add_generic(&self, pulses: &[Pulse]) -> Result<usize> {
+        let mut raw = pulses
+            .iter()
+            .map(|pulse| pigpiod_if2::gpioPulse_t {
+                gpioOn: pulse.on,
+                gpioOff: pulse.off,
+                usDelay: pulse.delay,
+            })
+            .collect::<Vec<_>>();
+
+        let count = unsafe {
+            pigpiod_if2::wave_add_generic(self.0 .0, raw.len() as ffi::c_uint, raw.as_mut_ptr())
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    // `stop_bits` counts half bits (2-8), `offset` delays the first start bit,
+    // returns the number of pulses in the wave so far
+    pub fn wave_add_serial(
+        &self,
+        gpio: Gpio,
+        baud: ffi::c_uint,
+        data_bits: ffi::c_uint,
+        stop_bits: ffi::c_uint,
+        offset: time::Duration,
+        bytes: &[u8],
+    ) -> Result<usize> {
+        let offset = ffi::c_uint::try_from(offset.as_micros())
+            .map_err(|_| Error::Pi(ErrorCode::BadSerOffset))?;
+        let pbuf = bytes.as_ptr().cast_mut().cast();
+        let buflen = bytes.len() as ffi::c_uint;
+
+        let count = unsafe {
+            pigpiod_if2::wave_add_serial(
+                self.0 .0, gpio.0, baud, data_bits, stop_bits, offset, buflen, pbuf,
+            )
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn wave_create(&self) -> Result<Wave<&Pi<Init>>> {
+        Wave::create(self)
+    }
+
+    pub fn wave_create_and_pad(&self, percent: u8) -> Result<Wave<&Pi<Init>>> {
+        Wave::create_and_pad(self, percent)
+    }
+
+    // `chain` is pigpio's chain script, wave ids mixed with loop and delay
+    // commands, the waves have to outlive the transmission
+    pub fn wave_chain(&self, chain: &[u8]) -> Result<()> {
+        let pbuf = chain.as_ptr().cast_mut().cast();
+        let buflen = chain.len() as ffi::c_uint;
+
+        let err = unsafe { pigpiod_if2::wave_chain(self.0 .0, pbuf, buflen) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    // id of the wave being sent, see `Wave::id`
+    pub fn wave_tx_at(&self) -> Result<Option<ffi::c_uint>> {
+        let id = unsafe { pigpiod_if2::wave_tx_at(self.0 .0) };
+
+        if id.is_negative() {
+            return Err(Error::new(id));
+        }
+
+        match id as ffi::c_uint {
+            pigpiod_if2::PI_NO_TX_WAVE | pigpiod_if2::PI_WAVE_NOT_FOUND => Ok(None),
+            id => Ok(Some(id)),
+        }
+    }
+
+    pub fn wave_tx_stop(&self) -> Result<()> {
+        let err = unsafe { pigpiod_if2::wave_tx_stop(self.0 .0) };
+
+        if err.is_negative() {
+            return Err(Error::new(err));
+        }
+
+        Ok(())
+    }
+
+    pub fn wave_tx_busy(&self) -> Result<bool> {
+        let busy = unsafe { pigpiod_if2::wave_tx_busy(self.0 .0) };
+
+        if busy.is_negative() {
+            return Err(Error::new(busy));
+        }
+
+        Ok(busy == 1)
+    }
+
+    // clocks the pulses out from the daemon and returns once they are done
+    pub fn send_pulses(&self, pulses: &[Pulse]) -> Result<()> {
+        // the daemon refuses to create empty waves
+        if pulses.is_empty() {
+            return Ok(());
+        }
+
+        self.wave_add_new()?;
+        self.wave_add_generic(pulses)?;
+
+        let wave = self.wave_create()?;
+        wave.send_once()?;
+
+        thread::sleep(pulses.iter().map(Pulse::delay).sum());
+        // a wave can't be deleted while it's being sent
+        while self.wave_tx_busy()? {
+            thread::sleep(time::Duration::from_millis(1));
+        }
+
+        Ok(())
+    }
+}
+
+impl Pi<Init> {
+    pub fn wave_get_micros(&self, stat: WaveStat) -> Result<u32> {
+        let count = unsafe {
+            match stat {
+                WaveStat::Current => pigpiod_if2::wave_get_micros(self.0 .0),
+                WaveStat::High => pigpiod_if2::wave_get_high_micros(self.0 .0),
+                WaveStat::Max => pigpiod_if2::wave_get_max_micros(self.0 .0),
+            }
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as u32)
+    }
+
+    pub fn wave_get_pulses(&self, stat: WaveStat) -> Result<u32> {
+        let count = unsafe {
+            match stat {
+                WaveStat::Current => pigpiod_if2::wave_get_pulses(self.0 .0),
+                WaveStat::High => pigpiod_if2::wave_get_high_pulses(self.0 .0),
+                WaveStat::Max => pigpiod_if2::wave_get_max_pulses(self.0 .0),
+            }
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as u32)
+    }
+
+    // DMA control blocks
+    pub fn wave_get_cbs(&self, stat: WaveStat) -> Result<u32> {
+        let count = unsafe {
+            match stat {
+                WaveStat::Current => pigpiod_if2::wave_get_cbs(self.0 .0),
+                WaveStat::High => pigpiod_if2::wave_get_high_cbs(self.0 .0),
+                WaveStat::Max => pigpiod_if2::wave_get_max_cbs(self.0 .0),
+            }
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as u32)
+    }
+}
+
+impl<C: PiConnection> Wave<C> {
+    // turns the pulses added so far into a wave
+    pub fn create(pi: C) -> Result<Self> {
+        let id = unsafe { pigpiod_if2::wave_create(pi.pi().0 .0) };
+
+        if id.is_negative() {
+            return Err(Error::new(id));
+        }
+
+        Ok(Self {
+            pi,
+            id: id as ffi::c_uint,
+        })
+    }
+
+    // reserves `percent` of the daemon's wave resources, so the wave can later
+    // be replaced by one of up to the same size while the others keep running
+    pub fn create_and_pad(pi: C, percent: u8) -> Result<Self> {
+        let id = unsafe { pigpiod_if2::wave_create_and_pad(pi.pi().0 .0, percent.into()) };
+
+        if id.is_negative() {
+            return Err(Error::new(id));
+        }
+
+        Ok(Self {
+            pi,
+            id: id as ffi::c_uint,
+        })
+    }
+
+    pub fn id(&self) -> ffi::c_uint {
+        self.id
+    }
+
+    // returns the number of DMA control blocks used
+    pub fn send_once(&self) -> Result<usize> {
+        let count = unsafe { pigpiod_if2::wave_send_once(self.pi.pi().0 .0, self.id) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    // repeats until another wave is sent or `wave_tx_stop`
+    pub fn send_repeat(&self) -> Result<usize> {
+        let count = unsafe { pigpiod_if2::wave_send_repeat(self.pi.pi().0 .0, self.id) };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn send_using_mode(&self, mode: WaveMode) -> Result<usize> {
+        let count = unsafe {
+            pigpiod_if2::wave_send_using_mode(self.pi.pi().0 .0, self.id, mode as ffi::c_uint)
+        };
+
+        if count.is_negative() {
+            return Err(Error::new(count));
+        }
+
+        Ok(count as usize)
+    }
+
+    pub fn delete(self) {}
+}
+
+impl<C: PiConnection> Drop for Wave<C> {
+    fn drop(&mut self) {
+        unsafe { pigpiod_if2::wave_delete(self.pi.pi().0 .0, self.id) };
+    }
+}
@@ -1,4 +1,5 @@
 // TODO only use needed
+pub use crate::backend::*;
 pub use crate::pi::*;
 pub use crate::segment_display::*;
 pub use crate::shift_register::*;
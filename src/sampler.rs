@@ -0,0 +1,106 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path;
+
+use anyhow::Result;
+use diesel::prelude::*;
+
+use crate::model::{NewTemperature, Temperature};
+use crate::prelude::*;
+
+// where the readings come from
+#[derive(Debug)]
+pub enum Sensor<C: PiConnection = Pi<Init>> {
+    // the kernel's w1-gpio driver, e.g. /sys/bus/w1/devices/<id>/temperature
+    W1(path::PathBuf),
+    OneWire(OneWire<C>, Rom),
+}
+
+impl<C: PiConnection> Sensor<C> {
+    // the id is the one the kernel would give the sensor
+    pub fn w1(device: &str) -> Self {
+        Self::W1(
+            path::PathBuf::from("/sys/bus/w1/devices")
+                .join(device)
+                .join("temperature"),
+        )
+    }
+
+    // in °C
+    pub fn read(&self, pi: &dyn GpioBackend) -> Result<f32> {
+        let temperature = match self {
+            Self::W1(path) => {
+                read_to_string(&pi, path)
+                    .map_err(misread)?
+                    .chars()
+                    .take_while(|&c| c != '\n')
+                    .collect::<String>()
+                    .parse::<f32>()?
+                    / 1000.
+            }
+            Self::OneWire(one_wire, rom) => one_wire.read_temperature(*rom).map_err(misread)?,
+        };
+
+        Ok(temperature)
+    }
+}
+
+// the sensor couldn't be read this round, e.g. a crc mismatch on the bus or
+// a w1 device that went away, the next round may well work
+#[derive(Debug)]
+pub struct Misread(pub Error);
+
+impl fmt::Display for Misread {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("couldn't read the sensor")
+    }
+}
+
+impl error::Error for Misread {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+// anything else, e.g. no permission to read the w1 file, won't go away
+fn misread(error: Error) -> anyhow::Error {
+    let transient = match &error {
+        Error::Pi(ErrorCode::FilOpenFailed | ErrorCode::NoFileMatch) => true,
+        Error::Io(error) => error.kind() == io::ErrorKind::NotFound,
+        Error::Other(error) => error.is::<BusError>(),
+        _ => false,
+    };
+
+    if transient {
+        Misread(error).into()
+    } else {
+        error.into()
+    }
+}
+
+// one round of the main loop: reads the sensor, shows the reading and
+// stores it
+pub fn sample<D, C>(
+    pi: &dyn GpioBackend,
+    display: &D,
+    conn: &mut SqliteConnection,
+    sensor: &Sensor<C>,
+) -> Result<Temperature>
+where
+    D: SegmentDisplay<4, String>,
+    C: PiConnection,
+{
+    let temperature = sensor.read(pi)?;
+
+    let width = 4 - temperature
+        .to_string()
+        .chars()
+        .take_while(|&c| c != '.')
+        .count();
+
+    display.write(format!("{temperature:.width$}"))?;
+
+    let row = NewTemperature::from(temperature).try_insert(conn)?;
+    Ok(row)
+}
@@ -94,46 +94,54 @@ static LETTERS: [u8; 26] = [
     0b1010_0100, // Z
 ];
 
-pub trait SegmentDisplay<'a, const N: usize, T> {
-    fn shift_register(&self) -> &ShiftRegister<'a, N>;
+pub trait SegmentDisplay<const N: usize, T> {
+    type Register: Register<N>;
+
+    fn shift_register(&self) -> &Self::Register;
     fn parse(value: T) -> [u8; N];
 
     fn write(&self, value: T) -> Result<()> {
-        self.shift_register().push_arr(Self::parse(value))?;
-        self.shift_register().save()?;
+        self.shift_register().load(Self::parse(value))?;
         Ok(())
     }
 }
 
-impl<'a, const N: usize, T> SegmentDisplay<'a, N, T> for ShiftRegister<'a, N>
+impl<const N: usize, T, R> SegmentDisplay<N, T> for R
 where
     T: fmt::Display,
+    R: Register<N>,
 {
-    fn shift_register(&self) -> &ShiftRegister<'a, N> {
+    type Register = Self;
+
+    fn shift_register(&self) -> &Self {
         self
     }
 
     fn parse(value: T) -> [u8; N] {
-        let string = format!("{value}");
+        to_segments(value)
+    }
+}
 
-        let left = string.chars();
-        let mut right = string.chars();
-        let first = right.next();
-        let right = right.chain(first);
+fn to_segments<const N: usize, T: fmt::Display>(value: T) -> [u8; N] {
+    let string = format!("{value}");
 
-        let both = left.zip(right);
+    let left = string.chars();
+    let mut right = string.chars();
+    let first = right.next();
+    let right = right.chain(first);
 
-        let mut deque = both
-            .filter_map(SegmentCode::new)
-            .take(N)
-            .map(SegmentCode::into)
-            .collect::<collections::VecDeque<u8>>();
+    let both = left.zip(right);
 
-        while deque.len() < N {
-            deque.push_front(0b1111_1111);
-        }
+    let mut deque = both
+        .filter_map(SegmentCode::new)
+        .take(N)
+        .map(SegmentCode::into)
+        .collect::<collections::VecDeque<u8>>();
 
-        // can't fail
-        deque.make_contiguous().try_into().unwrap()
+    while deque.len() < N {
+        deque.push_front(0b1111_1111);
     }
+
+    // can't fail
+    deque.make_contiguous().try_into().unwrap()
 }
@@ -1,57 +1,146 @@
+use std::cell;
+use std::ffi;
+use std::ops;
+use std::result;
+
+use embedded_hal::digital::OutputPin;
+
 use crate::prelude::*;
 
+// stands in for the optional OE and MR pins when they aren't wired up
 #[derive(Debug)]
-pub struct ShiftRegister<'a, const N: usize> {
-    pi: &'a Pi<Init>,
-    ds: Gpio,
-    sh_cp: Gpio,
-    st_cp: Gpio,
+pub struct NoPin;
+
+// 74HC595 chain on any three output pins, e.g. `Output`s on a pigpiod
+// connection or character device lines, see `builder`, or plain
+// embedded-hal pins, see `new`
+#[derive(Debug)]
+pub struct ShiftRegister<const N: usize, DS = Output, SH = DS, ST = DS, OE = NoPin, MR = NoPin> {
+    pins: cell::RefCell<Pins<DS, SH, ST, OE, MR>>,
 }
 
-impl<'a, const N: usize> ShiftRegister<'a, N> {
+#[derive(Debug)]
+struct Pins<DS, SH, ST, OE, MR> {
+    ds: DS,
+    sh_cp: SH,
+    st_cp: ST,
+    oe: OE,
+    mr: MR,
+}
+
+// long enough for a 74HC595, the shortest a wave can do anyway
+const PULSE_US: u32 = 1;
+
+impl<const N: usize> ShiftRegister<N> {
     pub fn builder() -> ShiftRegisterBuilder<N, NoPi, NoDs, NoShCp, NoStCp> {
-        ShiftRegisterBuilder {
-            pi: NoPi,
-            ds: NoDs,
-            sh_cp: NoShCp,
-            st_cp: NoStCp,
+        ShiftRegisterBuilder::new()
+    }
+}
+
+impl<const N: usize, DS, SH, ST> ShiftRegister<N, DS, SH, ST>
+where
+    DS: OutputPin,
+    SH: OutputPin<Error = DS::Error>,
+    ST: OutputPin<Error = DS::Error>,
+{
+    pub fn new(ds: DS, sh_cp: SH, st_cp: ST) -> Self {
+        Self {
+            pins: cell::RefCell::new(Pins {
+                ds,
+                sh_cp,
+                st_cp,
+                oe: NoPin,
+                mr: NoPin,
+            }),
         }
     }
+}
 
-    pub fn strobe(&self, gpio: Gpio) -> Result<()> {
-        self.pi.gpio_write(gpio, GpioLevel::Low)?;
-        self.pi.gpio_write(gpio, GpioLevel::High)?;
-        Ok(())
+impl<const N: usize, DS, SH, ST, MR> ShiftRegister<N, DS, SH, ST, NoPin, MR> {
+    // active low, the outputs stay as they are until `output_enable`
+    pub fn oe<OE>(self, oe: OE) -> ShiftRegister<N, DS, SH, ST, OE, MR>
+    where
+        DS: OutputPin,
+        OE: OutputPin<Error = DS::Error>,
+    {
+        let Pins {
+            ds,
+            sh_cp,
+            st_cp,
+            mr,
+            ..
+        } = self.pins.into_inner();
+        ShiftRegister {
+            pins: cell::RefCell::new(Pins {
+                ds,
+                sh_cp,
+                st_cp,
+                oe,
+                mr,
+            }),
+        }
     }
+}
 
-    pub fn shift(&self) -> Result<()> {
-        self.strobe(self.sh_cp)?;
-        Ok(())
+impl<const N: usize, DS, SH, ST, OE> ShiftRegister<N, DS, SH, ST, OE, NoPin> {
+    // active low
+    pub fn mr<MR>(self, mr: MR) -> ShiftRegister<N, DS, SH, ST, OE, MR>
+    where
+        DS: OutputPin,
+        MR: OutputPin<Error = DS::Error>,
+    {
+        let Pins {
+            ds,
+            sh_cp,
+            st_cp,
+            oe,
+            ..
+        } = self.pins.into_inner();
+        ShiftRegister {
+            pins: cell::RefCell::new(Pins {
+                ds,
+                sh_cp,
+                st_cp,
+                oe,
+                mr,
+            }),
+        }
     }
+}
 
-    pub fn save(&self) -> Result<()> {
-        self.strobe(self.st_cp)?;
+impl<const N: usize, DS, SH, ST, OE, MR> ShiftRegister<N, DS, SH, ST, OE, MR>
+where
+    DS: OutputPin,
+    SH: OutputPin<Error = DS::Error>,
+    ST: OutputPin<Error = DS::Error>,
+{
+    pub fn shift(&self) -> result::Result<(), DS::Error> {
+        let sh_cp = &mut self.pins.borrow_mut().sh_cp;
+        sh_cp.set_low()?;
+        sh_cp.set_high()?;
         Ok(())
     }
 
-    pub fn push(&self, byte: u8) -> Result<()> {
-        const MASK: u8 = 1;
+    pub fn save(&self) -> result::Result<(), DS::Error> {
+        let st_cp = &mut self.pins.borrow_mut().st_cp;
+        st_cp.set_low()?;
+        st_cp.set_high()?;
+        Ok(())
+    }
 
+    pub fn push(&self, byte: u8) -> result::Result<(), DS::Error> {
         for i in (0..8).rev() {
-            let level = if (byte >> i) & MASK == 1 {
-                GpioLevel::High
-            } else {
-                GpioLevel::Low
-            };
-
-            self.pi.gpio_write(self.ds, level)?;
+            self.pins
+                .borrow_mut()
+                .ds
+                .set_state(((byte >> i) & 1 == 1).into())?;
             self.shift()?;
         }
 
         Ok(())
     }
 
-    pub fn push_slice(&self, bytes: &[u8]) -> Result<()> {
+    pub fn push_slice(&self, bytes: &[u8]) -> result::Result<(), DS::Error> {
         for byte in bytes {
             self.push(*byte)?;
         }
@@ -59,11 +148,213 @@ impl<'a, const N: usize> ShiftRegister<'a, N> {
         Ok(())
     }
 
+    pub fn push_arr(&self, bytes: [u8; N]) -> result::Result<(), DS::Error> {
+        self.push_slice(&bytes)
+    }
+
+    pub fn load(&self, bytes: [u8; N]) -> result::Result<(), DS::Error> {
+        self.push_arr(bytes)?;
+        self.save()?;
+        Ok(())
+    }
+
+    pub fn clear(&self) -> result::Result<(), DS::Error> {
+        self.push_arr([0; N])
+    }
+
+    pub fn release(self) -> (DS, SH, ST, OE, MR) {
+        let Pins {
+            ds,
+            sh_cp,
+            st_cp,
+            oe,
+            mr,
+        } = self.pins.into_inner();
+        (ds, sh_cp, st_cp, oe, mr)
+    }
+}
+
+impl<const N: usize, DS, SH, ST, OE, MR> ShiftRegister<N, DS, SH, ST, OE, MR>
+where
+    DS: OutputPin,
+    OE: OutputPin<Error = DS::Error>,
+{
+    pub fn output_enable(&self) -> result::Result<(), DS::Error> {
+        self.pins.borrow_mut().oe.set_low()
+    }
+
+    pub fn output_disable(&self) -> result::Result<(), DS::Error> {
+        self.pins.borrow_mut().oe.set_high()
+    }
+}
+
+impl<const N: usize, DS, SH, ST, OE, MR> ShiftRegister<N, DS, SH, ST, OE, MR>
+where
+    DS: OutputPin,
+    MR: OutputPin<Error = DS::Error>,
+{
+    // clears the shift stage in one go, the outputs change with the next `save`
+    pub fn reset(&self) -> result::Result<(), DS::Error> {
+        let mr = &mut self.pins.borrow_mut().mr;
+        mr.set_low()?;
+        mr.set_high()?;
+        Ok(())
+    }
+}
+
+impl<const N: usize, B: GpioBackend, OE, MR>
+    ShiftRegister<N, Output<B>, Output<B>, Output<B>, OE, MR>
+{
+    // frames are sent to the backend as pulses instead of single writes,
+    // pigpiod clocks them out as a wave, which needs all pins to be 0-31,
+    // backends without `send_pulses` of their own still write them one by one
+    pub fn batched(self) -> BatchedShiftRegister<N, B, OE, MR> {
+        BatchedShiftRegister { sh_reg: self }
+    }
+}
+
+impl<const N: usize, DS, SH, ST, OE, MR> Register<N> for ShiftRegister<N, DS, SH, ST, OE, MR>
+where
+    DS: OutputPin,
+    SH: OutputPin<Error = DS::Error>,
+    ST: OutputPin<Error = DS::Error>,
+    DS::Error: Into<Error>,
+{
+    fn push_arr(&self, bytes: [u8; N]) -> Result<()> {
+        ShiftRegister::push_arr(self, bytes).map_err(Into::into)
+    }
+
+    fn save(&self) -> Result<()> {
+        ShiftRegister::save(self).map_err(Into::into)
+    }
+}
+
+// a `ShiftRegister` on a backend's own pins that hands it whole frames,
+// everything but pushing goes to the plain register
+#[derive(Debug)]
+pub struct BatchedShiftRegister<const N: usize, B: GpioBackend, OE = NoPin, MR = NoPin> {
+    sh_reg: ShiftRegister<N, Output<B>, Output<B>, Output<B>, OE, MR>,
+}
+
+impl<const N: usize, B: GpioBackend, OE, MR> BatchedShiftRegister<N, B, OE, MR> {
     pub fn push_arr(&self, bytes: [u8; N]) -> Result<()> {
-        for byte in bytes {
-            self.push(byte)?;
+        let pins = self.sh_reg.pins.borrow();
+        pins.ds.pi().send_pulses(&frame(&pins, &bytes)?)
+    }
+
+    // pushes and latches a whole frame in one go
+    pub fn load(&self, bytes: [u8; N]) -> Result<()> {
+        let pins = self.sh_reg.pins.borrow();
+        let mut pulses = frame(&pins, &bytes)?;
+        pulses.extend(latch(&pins)?);
+        pins.ds.pi().send_pulses(&pulses)
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        self.push_arr([0; N])
+    }
+
+    pub fn unbatched(self) -> ShiftRegister<N, Output<B>, Output<B>, Output<B>, OE, MR> {
+        self.sh_reg
+    }
+}
+
+impl<const N: usize, B: GpioBackend, OE, MR> ops::Deref for BatchedShiftRegister<N, B, OE, MR> {
+    type Target = ShiftRegister<N, Output<B>, Output<B>, Output<B>, OE, MR>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sh_reg
+    }
+}
+
+impl<const N: usize, B: GpioBackend, OE, MR> Register<N> for BatchedShiftRegister<N, B, OE, MR> {
+    fn push_arr(&self, bytes: [u8; N]) -> Result<()> {
+        BatchedShiftRegister::push_arr(self, bytes)
+    }
+
+    fn save(&self) -> Result<()> {
+        self.sh_reg.save()
+    }
+
+    fn load(&self, bytes: [u8; N]) -> Result<()> {
+        BatchedShiftRegister::load(self, bytes)
+    }
+}
+
+type OutputPins<B, OE, MR> = Pins<Output<B>, Output<B>, Output<B>, OE, MR>;
+
+// the same sequence of levels `push` writes one by one
+fn frame<B: GpioBackend, OE, MR>(pins: &OutputPins<B, OE, MR>, bytes: &[u8]) -> Result<Vec<Pulse>> {
+    let mut pulses = Vec::with_capacity(bytes.len() * 16);
+
+    for byte in bytes {
+        for i in (0..8).rev() {
+            let level = GpioLevel::from((byte >> i) & 1 == 1);
+
+            pulses.push(
+                Pulse::new(PULSE_US)
+                    .set(pins.ds.gpio(), level)?
+                    .set(pins.sh_cp.gpio(), GpioLevel::Low)?,
+            );
+            pulses.push(Pulse::new(PULSE_US).set(pins.sh_cp.gpio(), GpioLevel::High)?);
         }
+    }
+
+    Ok(pulses)
+}
 
+fn latch<B: GpioBackend, OE, MR>(pins: &OutputPins<B, OE, MR>) -> Result<[Pulse; 2]> {
+    Ok([
+        Pulse::new(PULSE_US).set(pins.st_cp.gpio(), GpioLevel::Low)?,
+        Pulse::new(PULSE_US).set(pins.st_cp.gpio(), GpioLevel::High)?,
+    ])
+}
+
+// what a display needs from whatever drives the register chain
+pub trait Register<const N: usize> {
+    fn push_arr(&self, bytes: [u8; N]) -> Result<()>;
+    fn save(&self) -> Result<()>;
+
+    fn load(&self, bytes: [u8; N]) -> Result<()> {
+        self.push_arr(bytes)?;
+        self.save()?;
+        Ok(())
+    }
+}
+
+// DS and SH_CP wired to MOSI and SCLK, the whole frame goes out in one transfer
+#[derive(Debug)]
+pub struct SpiShiftRegister<const N: usize, C: PiConnection = Pi<Init>> {
+    spi: Spi<C>,
+    st_cp: Gpio,
+}
+
+impl<const N: usize, C: PiConnection> SpiShiftRegister<N, C> {
+    // the 74HC595 samples on the rising clock edge, which is SPI mode 0
+    pub fn open(pi: C, channel: ffi::c_uint, baud: ffi::c_uint, st_cp: Gpio) -> Result<Self> {
+        let spi = Spi::open(pi, channel, baud, 0)?;
+
+        // from here on the latch pin is released again when dropped
+        let sh_reg = SpiShiftRegister { spi, st_cp };
+        sh_reg.spi.pi().set_mode(st_cp, GpioMode::Output)?;
+
+        Ok(sh_reg)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let pi = self.spi.pi();
+        pi.gpio_write(self.st_cp, GpioLevel::Low)?;
+        pi.gpio_write(self.st_cp, GpioLevel::High)?;
+        Ok(())
+    }
+
+    pub fn push_slice(&self, bytes: &[u8]) -> Result<()> {
+        self.spi.write(bytes)?;
+        Ok(())
+    }
+
+    pub fn push_arr(&self, bytes: [u8; N]) -> Result<()> {
+        self.push_slice(&bytes)?;
         Ok(())
     }
 
@@ -73,6 +364,22 @@ impl<'a, const N: usize> ShiftRegister<'a, N> {
     }
 }
 
+impl<const N: usize, C: PiConnection> Register<N> for SpiShiftRegister<N, C> {
+    fn push_arr(&self, bytes: [u8; N]) -> Result<()> {
+        SpiShiftRegister::push_arr(self, bytes)
+    }
+
+    fn save(&self) -> Result<()> {
+        SpiShiftRegister::save(self)
+    }
+}
+
+impl<const N: usize, C: PiConnection> Drop for SpiShiftRegister<N, C> {
+    fn drop(&mut self) {
+        let _ = self.spi.pi().set_mode(self.st_cp, GpioMode::Input);
+    }
+}
+
 pub struct NoPi;
 pub struct NoDs;
 pub struct NoShCp;
@@ -83,6 +390,7 @@ pub struct ShiftRegisterBuilder<const N: usize, T, U, V, W> {
     ds: U,
     sh_cp: V,
     st_cp: W,
+    idle: GpioLevel,
 }
 
 impl<const N: usize> ShiftRegisterBuilder<N, NoPi, NoDs, NoShCp, NoStCp> {
@@ -92,6 +400,7 @@ impl<const N: usize> ShiftRegisterBuilder<N, NoPi, NoDs, NoShCp, NoStCp> {
             ds: NoDs,
             sh_cp: NoShCp,
             st_cp: NoStCp,
+            idle: GpioLevel::Low,
         }
     }
 }
@@ -103,67 +412,102 @@ impl<const N: usize> Default for ShiftRegisterBuilder<N, NoPi, NoDs, NoShCp, NoS
 }
 
 impl<const N: usize, T, U, V, W> ShiftRegisterBuilder<N, T, U, V, W> {
-    pub fn pi(self, pi: &Pi<Init>) -> ShiftRegisterBuilder<N, &Pi<Init>, U, V, W> {
+    // takes a reference or an owned handle like `SharedPi`
+    pub fn pi<B: GpioBackend>(self, pi: B) -> ShiftRegisterBuilder<N, B, U, V, W> {
         let Self {
-            ds, sh_cp, st_cp, ..
+            ds,
+            sh_cp,
+            st_cp,
+            idle,
+            ..
         } = self;
         ShiftRegisterBuilder {
             pi,
             ds,
             sh_cp,
             st_cp,
+            idle,
         }
     }
 
     pub fn ds(self, ds: Gpio) -> ShiftRegisterBuilder<N, T, Gpio, V, W> {
         let Self {
-            pi, sh_cp, st_cp, ..
+            pi,
+            sh_cp,
+            st_cp,
+            idle,
+            ..
         } = self;
         ShiftRegisterBuilder {
             pi,
             ds,
             sh_cp,
             st_cp,
+            idle,
         }
     }
 
     pub fn sh_cp(self, sh_cp: Gpio) -> ShiftRegisterBuilder<N, T, U, Gpio, W> {
-        let Self { pi, ds, st_cp, .. } = self;
+        let Self {
+            pi,
+            ds,
+            st_cp,
+            idle,
+            ..
+        } = self;
         ShiftRegisterBuilder {
             pi,
             ds,
             sh_cp,
             st_cp,
+            idle,
         }
     }
 
     pub fn st_cp(self, st_cp: Gpio) -> ShiftRegisterBuilder<N, T, U, V, Gpio> {
-        let Self { pi, ds, sh_cp, .. } = self;
+        let Self {
+            pi,
+            ds,
+            sh_cp,
+            idle,
+            ..
+        } = self;
         ShiftRegisterBuilder {
             pi,
             ds,
             sh_cp,
             st_cp,
+            idle,
+        }
+    }
+
+    // level the pins are driven to right after they are configured, low
+    // unless set
+    pub fn idle(self, level: GpioLevel) -> Self {
+        Self {
+            idle: level,
+            ..self
         }
     }
 }
 
-impl<'a, const N: usize> ShiftRegisterBuilder<N, &'a Pi<Init>, Gpio, Gpio, Gpio> {
-    pub fn build(self) -> Result<ShiftRegister<'a, N>> {
+// every pin gets its own handle on the backend, so it has to be a reference
+// or a shared one like `SharedPi`
+impl<const N: usize, B: GpioBackend + Clone> ShiftRegisterBuilder<N, B, Gpio, Gpio, Gpio> {
+    pub fn build(self) -> Result<ShiftRegister<N, Output<B>>> {
         let Self {
             pi,
             ds,
             sh_cp,
             st_cp,
+            idle,
         } = self;
-        pi.set_mode(ds, GpioMode::Input)?;
-        pi.set_mode(sh_cp, GpioMode::Input)?;
-        pi.set_mode(st_cp, GpioMode::Input)?;
-        Ok(ShiftRegister {
-            pi,
-            ds,
-            sh_cp,
-            st_cp,
-        })
+
+        // the pins already configured are released again if one fails
+        let ds = Output::new(pi.clone(), ds, idle)?;
+        let sh_cp = Output::new(pi.clone(), sh_cp, idle)?;
+        let st_cp = Output::new(pi, st_cp, idle)?;
+
+        Ok(ShiftRegister::new(ds, sh_cp, st_cp))
     }
 }
@@ -0,0 +1,50 @@
+#![cfg(feature = "tokio")]
+
+use std::time;
+
+use cps::daemon::FakeDaemon;
+use cps::prelude::*;
+
+fn gpio(gpio: u32) -> Gpio {
+    Gpio::new(gpio).unwrap()
+}
+
+async fn connect(daemon: &FakeDaemon) -> AsyncPi {
+    AsyncPi::connect("127.0.0.1", &daemon.port().to_string())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn writes_read_back() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = connect(&daemon).await;
+
+    pi.set_mode(gpio(4), GpioMode::Output).await.unwrap();
+    pi.gpio_write(gpio(4), GpioLevel::High).await.unwrap();
+    assert_eq!(pi.gpio_read(gpio(4)).await.unwrap(), GpioLevel::High);
+    assert_eq!(daemon.backend().level(gpio(4)), Some(GpioLevel::High));
+
+    pi.gpio_write(gpio(4), GpioLevel::Low).await.unwrap();
+    assert_eq!(pi.gpio_read(gpio(4)).await.unwrap(), GpioLevel::Low);
+}
+
+#[tokio::test]
+async fn edges_are_streamed() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = connect(&daemon).await;
+
+    let mut edges = pi.edges(gpio(5), Edge::Rising).await.unwrap();
+    tokio::time::sleep(time::Duration::from_millis(20)).await;
+
+    for _ in 0..2 {
+        daemon.backend().set_level(gpio(5), GpioLevel::High);
+        let next = tokio::time::timeout(time::Duration::from_secs(2), edges.next());
+        let (level, _) = next.await.unwrap().unwrap().unwrap();
+        assert_eq!(level, GpioLevel::High);
+
+        // falling edges are filtered out
+        daemon.backend().set_level(gpio(5), GpioLevel::Low);
+        tokio::time::sleep(time::Duration::from_millis(20)).await;
+    }
+}
@@ -0,0 +1,72 @@
+use cps::daemon::FakeDaemon;
+use cps::prelude::*;
+
+fn gpio(gpio: u32) -> Gpio {
+    Gpio::new(gpio).unwrap()
+}
+
+#[test]
+fn bank_1_round_trips() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+
+    let mask = GpioMask::from_gpios([gpio(4), gpio(17), gpio(31)]).unwrap();
+    assert!(GpioMask::new().with(gpio(32)).is_err());
+    assert_eq!(mask.bits(), 1 << 4 | 1 << 17 | 1 << 31);
+    assert!(mask.contains(gpio(17)));
+    assert!(!mask.contains(gpio(18)));
+    assert_eq!(
+        mask.gpios().collect::<Vec<_>>(),
+        [gpio(4), gpio(17), gpio(31)]
+    );
+
+    assert!(pi.read_bank_1().unwrap().is_empty());
+    pi.set_bank_1(mask).unwrap();
+    assert_eq!(pi.read_bank_1().unwrap(), mask);
+    assert_eq!(daemon.backend().level(gpio(17)), Some(GpioLevel::High));
+
+    pi.clear_bank_1(GpioMask::new().with(gpio(17)).unwrap())
+        .unwrap();
+    assert_eq!(pi.read_bank_1().unwrap().bits(), 1 << 4 | 1 << 31);
+    assert_eq!(daemon.backend().level(gpio(17)), Some(GpioLevel::Low));
+
+    // levels set elsewhere show up too
+    daemon.backend().set_level(gpio(2), GpioLevel::High);
+    assert!(pi.read_bank_1().unwrap().contains(gpio(2)));
+}
+
+#[test]
+fn bank_2_round_trips() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+
+    let mask = Bank2Mask::from_gpios([gpio(32), gpio(40), gpio(53)]).unwrap();
+    assert!(Bank2Mask::new().with(gpio(31)).is_err());
+    assert_eq!(mask.bits(), 1 | 1 << 8 | 1 << 21);
+    assert_eq!(
+        mask.gpios().collect::<Vec<_>>(),
+        [gpio(32), gpio(40), gpio(53)]
+    );
+
+    pi.set_bank_2(mask).unwrap();
+    assert_eq!(pi.read_bank_2().unwrap(), mask);
+    assert_eq!(daemon.backend().level(gpio(40)), Some(GpioLevel::High));
+
+    pi.clear_bank_2(Bank2Mask::new().with(gpio(40)).unwrap())
+        .unwrap();
+    assert_eq!(pi.read_bank_2().unwrap().bits(), 1 | 1 << 21);
+    assert_eq!((!mask).bits(), 0x3f_ffff ^ mask.bits());
+}
+
+// the C library hands back its error codes as levels
+#[cfg(feature = "socket")]
+#[test]
+fn lost_connections_are_errors() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+    drop(daemon);
+
+    assert!(pi.read_bank_1().is_err());
+    assert!(pi.read_bank_2().is_err());
+    assert!(pi.current_tick().is_err());
+}
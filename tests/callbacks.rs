@@ -0,0 +1,33 @@
+use std::sync;
+use std::sync::mpsc;
+use std::time;
+
+use cps::daemon::FakeDaemon;
+use cps::prelude::*;
+
+fn gpio(gpio: u32) -> Gpio {
+    Gpio::new(gpio).unwrap()
+}
+
+#[test]
+fn callbacks_can_cancel_callbacks() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi: SharedPi = sync::Arc::new(daemon.connect().unwrap());
+
+    let other = Callback::new(pi.clone(), gpio(6), Edge::Either, |_, _, _| {}).unwrap();
+    let other = sync::Mutex::new(Some(other));
+
+    let (tx, rx) = mpsc::channel();
+    let callback_pi = pi.clone();
+    let _callback = Callback::new(pi, gpio(5), Edge::Rising, move |_, _, _| {
+        drop(other.lock().unwrap().take());
+        // registering works from here too
+        let again = callback_pi.callback(gpio(7), Edge::Either, |_, _, _| {});
+        let _ = tx.send(again.is_ok());
+    })
+    .unwrap();
+
+    daemon.backend().set_level(gpio(5), GpioLevel::High);
+    let registered = rx.recv_timeout(time::Duration::from_secs(2));
+    assert_eq!(registered, Ok(true));
+}
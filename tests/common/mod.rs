@@ -0,0 +1,78 @@
+// not every test uses every helper
+#![allow(dead_code)]
+
+use cps::mock::Event;
+use cps::prelude::*;
+
+pub const DS: u32 = 17;
+pub const SH_CP: u32 = 22;
+pub const ST_CP: u32 = 27;
+
+pub fn gpio(gpio: u32) -> Gpio {
+    Gpio::new(gpio).unwrap()
+}
+
+pub fn sh_reg<B: GpioBackend + Clone>(pi: B) -> ShiftRegister<4, Output<B>> {
+    ShiftRegister::<4>::builder()
+        .pi(pi)
+        .ds(gpio(DS))
+        .sh_cp(gpio(SH_CP))
+        .st_cp(gpio(ST_CP))
+        .build()
+        .unwrap()
+}
+
+// what a chain of 74HC595s shows after `events`, in the order the bytes were pushed
+pub fn latched<const N: usize>(events: &[Event]) -> [u8; N] {
+    let mut ds = GpioLevel::Low;
+    let mut sh_cp = GpioLevel::Low;
+    let mut st_cp = GpioLevel::Low;
+    let mut shifted = Vec::new();
+    let mut latched = Vec::new();
+
+    for event in events {
+        let Event::Write(pin, level) = *event else {
+            continue;
+        };
+
+        match pin.get() {
+            DS => ds = level,
+            SH_CP => {
+                if sh_cp == GpioLevel::Low && level == GpioLevel::High {
+                    shifted.push(ds == GpioLevel::High);
+                }
+                sh_cp = level;
+            }
+            ST_CP => {
+                if st_cp == GpioLevel::Low && level == GpioLevel::High {
+                    latched = shifted[shifted.len().saturating_sub(N * 8)..].to_vec();
+                }
+                st_cp = level;
+            }
+            _ => panic!("unexpected write to {pin:?}"),
+        }
+    }
+
+    let bytes = latched
+        .chunks(8)
+        .map(|bits| bits.iter().fold(0, |byte, &bit| byte << 1 | u8::from(bit)))
+        .collect::<Vec<_>>();
+    bytes.try_into().unwrap()
+}
+
+// every bit msb first, clocked in on the rising edge, then latched
+pub fn frame(bytes: &[u8]) -> Vec<Event> {
+    let mut events = Vec::new();
+
+    for byte in bytes {
+        for i in (0..8).rev() {
+            events.push(Event::Write(gpio(DS), GpioLevel::from(byte >> i & 1 == 1)));
+            events.push(Event::Write(gpio(SH_CP), GpioLevel::Low));
+            events.push(Event::Write(gpio(SH_CP), GpioLevel::High));
+        }
+    }
+
+    events.push(Event::Write(gpio(ST_CP), GpioLevel::Low));
+    events.push(Event::Write(gpio(ST_CP), GpioLevel::High));
+    events
+}
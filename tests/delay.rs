@@ -0,0 +1,32 @@
+use std::time;
+
+use cps::daemon::FakeDaemon;
+use cps::prelude::*;
+use embedded_hal::delay::DelayNs;
+
+#[test]
+fn delays_follow_the_daemon() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = ReconnectingPi::new("127.0.0.1", &daemon.port().to_string()).unwrap();
+    let mut delay = Delay::new(&pi);
+
+    let start = time::Instant::now();
+    delay.delay_ms(20);
+    assert!(start.elapsed() >= time::Duration::from_millis(20));
+}
+
+#[test]
+fn delays_outlive_the_daemon() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = ReconnectingPi::new("127.0.0.1", &daemon.port().to_string()).unwrap();
+    let mut delay = Delay::new(&pi);
+    drop(daemon);
+
+    assert!(pi.current_tick().is_err());
+
+    let start = time::Instant::now();
+    delay.delay_ms(20);
+    let elapsed = start.elapsed();
+    assert!(elapsed >= time::Duration::from_millis(20));
+    assert!(elapsed < time::Duration::from_secs(1));
+}
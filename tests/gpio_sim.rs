@@ -0,0 +1,69 @@
+// needs a simulated chip and write access to its sysfs attributes:
+//
+//   sudo scripts/gpio-sim.sh up
+//   CPS_GPIO_SIM_CHIP=/dev/gpiochipN cargo test --features gpiochip -- --ignored
+#![cfg(all(feature = "gpiochip", target_os = "linux"))]
+
+use std::env;
+use std::fs;
+use std::path;
+
+use cps::gpiochip::GpioChip;
+use cps::prelude::*;
+
+fn chip() -> (GpioChip, path::PathBuf) {
+    let chip = env::var("CPS_GPIO_SIM_CHIP").expect("CPS_GPIO_SIM_CHIP isn't set");
+    let name = path::Path::new(&chip).file_name().unwrap().to_owned();
+    let sysfs = path::Path::new("/sys/bus/gpio/devices").join(name);
+
+    (GpioChip::open(&chip).unwrap(), sysfs)
+}
+
+fn attribute(sysfs: &path::Path, gpio: Gpio, name: &str) -> path::PathBuf {
+    sysfs.join(format!("sim_gpio{}", gpio.get())).join(name)
+}
+
+#[test]
+#[ignore = "needs a gpio-sim chip in CPS_GPIO_SIM_CHIP"]
+fn writes_show_up_in_the_sim() {
+    let (chip, sysfs) = chip();
+    let gpio = Gpio::new(17).unwrap();
+    let value = attribute(&sysfs, gpio, "value");
+
+    chip.set_mode(gpio, GpioMode::Output).unwrap();
+
+    for (level, expected) in [(GpioLevel::High, "1"), (GpioLevel::Low, "0")] {
+        chip.gpio_write(gpio, level).unwrap();
+        assert_eq!(fs::read_to_string(&value).unwrap().trim(), expected);
+        assert_eq!(chip.gpio_read(gpio).unwrap(), level);
+    }
+}
+
+#[test]
+#[ignore = "needs a gpio-sim chip in CPS_GPIO_SIM_CHIP"]
+fn reads_follow_the_sim_pull() {
+    let (chip, sysfs) = chip();
+    let gpio = Gpio::new(22).unwrap();
+    let pull = attribute(&sysfs, gpio, "pull");
+
+    chip.set_mode(gpio, GpioMode::Input).unwrap();
+
+    for (bias, expected) in [("pull-up", GpioLevel::High), ("pull-down", GpioLevel::Low)] {
+        fs::write(&pull, bias).unwrap();
+        assert_eq!(chip.gpio_read(gpio).unwrap(), expected);
+    }
+}
+
+#[test]
+#[ignore = "needs a gpio-sim chip in CPS_GPIO_SIM_CHIP"]
+fn outputs_keep_their_level_when_switched() {
+    let (chip, sysfs) = chip();
+    let gpio = Gpio::new(27).unwrap();
+
+    chip.set_mode(gpio, GpioMode::Input).unwrap();
+    fs::write(attribute(&sysfs, gpio, "pull"), "pull-up").unwrap();
+    chip.set_mode(gpio, GpioMode::Output).unwrap();
+
+    let value = fs::read_to_string(attribute(&sysfs, gpio, "value")).unwrap();
+    assert_eq!(value.trim(), "1");
+}
@@ -0,0 +1,84 @@
+use cps::prelude::*;
+
+fn scratchpad(bytes: [u8; 8]) -> [u8; 9] {
+    let mut scratchpad = [0; 9];
+    scratchpad[..8].copy_from_slice(&bytes);
+    scratchpad[8] = crc8(&bytes);
+    scratchpad
+}
+
+fn bus_error(result: Result<f32>) -> Option<BusError> {
+    match result {
+        Err(Error::Other(error)) => error.downcast_ref::<BusError>().copied(),
+        _ => None,
+    }
+}
+
+#[test]
+fn crc_of_the_application_note_example() {
+    assert_eq!(crc8(&[0x02, 0x1c, 0xb8, 0x01, 0x00, 0x00, 0x00]), 0xa2);
+    assert_eq!(crc8(&[]), 0);
+}
+
+#[test]
+fn roms_check_their_crc() {
+    let rom = 0xa2_00_00_00_01_b8_1c_02;
+    assert_eq!(Rom::new(rom).map(Rom::get), Some(rom));
+    assert_eq!(Rom::new(rom ^ 1 << 60), None);
+    assert_eq!(Rom::new(rom ^ 1), None);
+}
+
+#[test]
+fn roms_are_named_like_the_kernel_does() {
+    let rom = "10-00080253aa82".parse::<Rom>().unwrap();
+    assert_eq!(rom.family(), 0x10);
+    assert_eq!(rom.serial(), 0x0008_0253_aa82);
+    assert_eq!(rom.to_string(), "10-00080253aa82");
+    assert_eq!(Rom::new(rom.get()), Some(rom));
+
+    assert!("1000080253aa82".parse::<Rom>().is_err());
+    assert!("10-1000000000000".parse::<Rom>().is_err());
+    assert!("xx-00080253aa82".parse::<Rom>().is_err());
+}
+
+#[test]
+fn ds18s20_temperatures() {
+    // the datasheet's example, 25°C refined by the counter
+    let pad = scratchpad([0x32, 0x00, 0x4b, 0x46, 0xff, 0xff, 0x0c, 0x10]);
+    assert_eq!(temperature(0x10, pad).unwrap(), 25.);
+
+    // -0.5°C
+    let pad = scratchpad([0xff, 0xff, 0x4b, 0x46, 0xff, 0xff, 0x04, 0x10]);
+    assert_eq!(temperature(0x10, pad).unwrap(), -0.5);
+}
+
+#[test]
+fn ds18b20_temperatures() {
+    let pad = scratchpad([0x91, 0x01, 0x4b, 0x46, 0x7f, 0xff, 0x0f, 0x10]);
+    assert_eq!(temperature(0x28, pad).unwrap(), 25.0625);
+
+    let pad = scratchpad([0x5e, 0xff, 0x4b, 0x46, 0x7f, 0xff, 0x02, 0x10]);
+    assert_eq!(temperature(0x28, pad).unwrap(), -10.125);
+}
+
+#[test]
+fn bad_scratchpads() {
+    let mut pad = scratchpad([0x32, 0x00, 0x4b, 0x46, 0xff, 0xff, 0x0c, 0x10]);
+    pad[0] ^= 1;
+    assert_eq!(
+        bus_error(temperature(0x10, pad)),
+        Some(BusError::CrcMismatch)
+    );
+
+    // a line held low reads all zeros, which passes the crc
+    assert_eq!(
+        bus_error(temperature(0x28, [0; 9])),
+        Some(BusError::BadScratchpad)
+    );
+
+    let pad = scratchpad([0x32, 0x00, 0x4b, 0x46, 0xff, 0xff, 0x0c, 0x00]);
+    assert_eq!(
+        bus_error(temperature(0x10, pad)),
+        Some(BusError::BadScratchpad)
+    );
+}
@@ -0,0 +1,113 @@
+use std::sync;
+use std::thread;
+use std::time;
+
+use cps::daemon::FakeDaemon;
+use cps::prelude::*;
+
+const PIN: u32 = 17;
+
+fn ms(ms: u64) -> time::Duration {
+    time::Duration::from_millis(ms)
+}
+
+#[test]
+fn counts_only_inside_the_window() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+    let gpio = Gpio::new(PIN).unwrap();
+    let backend = daemon.backend();
+
+    let mut notify = Notify::open_in_band(&pi, daemon.addr()).unwrap();
+    notify.begin(GpioMask::new().with(gpio).unwrap()).unwrap();
+    thread::sleep(ms(20));
+
+    // queued up before the window starts, each one reported
+    backend.set_level(gpio, GpioLevel::High);
+    thread::sleep(ms(20));
+    backend.set_level(gpio, GpioLevel::Low);
+    thread::sleep(ms(20));
+
+    let mut counter = PulseCounter::new(gpio, Edge::Rising);
+    thread::scope(|s| {
+        s.spawn(|| {
+            let start = time::Instant::now();
+            for (at, level) in [
+                (25, GpioLevel::High),
+                (50, GpioLevel::Low),
+                (75, GpioLevel::High),
+                (125, GpioLevel::Low),
+            ] {
+                thread::sleep(ms(at).saturating_sub(start.elapsed()));
+                backend.set_level(gpio, level);
+            }
+        });
+
+        notify.count_pulses(&mut counter, ms(100)).unwrap();
+    });
+
+    assert_eq!(counter.count(), 2);
+
+    // the edge that closed the window is still there for the next one
+    match notify.next().unwrap().unwrap() {
+        Notification::Level { level, .. } => assert_eq!(level, GpioLevel::Low),
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn quiet_pins_end_the_window_too() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+    let gpio = Gpio::new(PIN).unwrap();
+
+    let mut notify = Notify::open_in_band(&pi, daemon.addr()).unwrap();
+    notify.begin(GpioMask::new().with(gpio).unwrap()).unwrap();
+
+    let mut counter = PulseCounter::new(gpio, Edge::Rising);
+    let start = time::Instant::now();
+    notify.count_pulses(&mut counter, ms(100)).unwrap();
+
+    assert!(start.elapsed() >= ms(100));
+    assert!(start.elapsed() < ms(1000));
+    assert_eq!(counter.count(), 0);
+}
+
+#[test]
+fn ended_notifications_are_errors() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+    let gpio = Gpio::new(PIN).unwrap();
+
+    let mut notify = Notify::open_in_band(&pi, daemon.addr()).unwrap();
+    notify.begin(GpioMask::new().with(gpio).unwrap()).unwrap();
+
+    let mut counter = PulseCounter::new(gpio, Edge::Rising);
+    thread::scope(|s| {
+        s.spawn(|| {
+            thread::sleep(ms(50));
+            drop(daemon);
+        });
+
+        assert!(notify.count_pulses(&mut counter, ms(1000)).is_err());
+    });
+}
+
+#[test]
+fn notifications_can_own_the_connection() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi: SharedPi = sync::Arc::new(daemon.connect().unwrap());
+    let gpio = Gpio::new(PIN).unwrap();
+
+    let mut notify = Notify::open_in_band(pi, daemon.addr()).unwrap();
+    notify.begin(GpioMask::new().with(gpio).unwrap()).unwrap();
+    thread::sleep(ms(20));
+
+    let reader = thread::spawn(move || notify.next().unwrap().unwrap());
+    daemon.backend().set_level(gpio, GpioLevel::High);
+
+    match reader.join().unwrap() {
+        Notification::Level { level, .. } => assert_eq!(level, GpioLevel::High),
+        other => panic!("{other:?}"),
+    }
+}
@@ -0,0 +1,44 @@
+use cps::daemon::FakeDaemon;
+use cps::mock::{Event, Mock};
+use cps::prelude::*;
+
+fn gpio(gpio: u32) -> Gpio {
+    Gpio::new(gpio).unwrap()
+}
+
+#[test]
+fn waves_write_every_level() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+
+    let pulses = [
+        Pulse::new(10)
+            .set(gpio(2), GpioLevel::High)
+            .unwrap()
+            .set(gpio(3), GpioLevel::Low)
+            .unwrap(),
+        Pulse::new(10).set(gpio(2), GpioLevel::Low).unwrap(),
+    ];
+    pi.send_pulses(&pulses).unwrap();
+
+    assert_eq!(
+        daemon.backend().take_events(),
+        [
+            Event::Write(gpio(2), GpioLevel::High),
+            Event::Write(gpio(3), GpioLevel::Low),
+            Event::Write(gpio(2), GpioLevel::Low),
+        ]
+    );
+}
+
+#[test]
+fn nothing_to_send() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi = daemon.connect().unwrap();
+    pi.send_pulses(&[]).unwrap();
+    assert_eq!(daemon.backend().take_events(), []);
+
+    let mock = Mock::new();
+    mock.send_pulses(&[]).unwrap();
+    assert_eq!(mock.take_events(), []);
+}
@@ -0,0 +1,19 @@
+use cps::prelude::*;
+
+#[test]
+fn software_and_hardware_limits_differ() {
+    assert_eq!(DutyCycle::new(40_000).map(DutyCycle::get), Some(40_000));
+    assert_eq!(DutyCycle::new(40_001), None);
+    assert_eq!(
+        HwDutyCycle::new(1_000_000).map(HwDutyCycle::get),
+        Some(1_000_000)
+    );
+    assert_eq!(HwDutyCycle::new(1_000_001), None);
+
+    assert_eq!(Frequency::new(0), None);
+    assert_eq!(Frequency::new(8_000).map(Frequency::get), Some(8_000));
+    assert_eq!(Frequency::new(40_001), None);
+    assert_eq!(HwFrequency::new(0), None);
+    assert_eq!(HwFrequency::new(40_001).map(HwFrequency::get), Some(40_001));
+    assert_eq!(HwFrequency::new(187_500_001), None);
+}
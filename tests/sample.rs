@@ -0,0 +1,90 @@
+mod common;
+
+use common::{frame, gpio, latched, sh_reg, DS, SH_CP, ST_CP};
+use cps::daemon::FakeDaemon;
+use cps::mock::{Event, Mock};
+use cps::prelude::*;
+use cps::sampler::{sample, Misread, Sensor};
+use cps::schema::temperatures;
+use diesel::prelude::*;
+
+const DEVICE: &str = "10-00080253aa82";
+
+fn database() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query(include_str!(
+        "../migrations/2024-05-18-114347_create_temperatures/up.sql"
+    ))
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+#[test]
+fn samples_through_the_daemon() {
+    let path = format!("/sys/bus/w1/devices/{DEVICE}/temperature");
+    let daemon = FakeDaemon::start(Mock::new().with_file(path, "21500\n")).unwrap();
+    let pi =
+        ReconnectingPi::new(&daemon.addr().ip().to_string(), &daemon.port().to_string()).unwrap();
+    let pi: &dyn GpioBackend = &pi;
+
+    let sh_reg = sh_reg(pi);
+    let modes = daemon.backend().take_events();
+    for pin in [DS, SH_CP, ST_CP] {
+        assert!(modes.contains(&Event::Mode(gpio(pin), GpioMode::Output)));
+    }
+
+    let mut conn = database();
+    let sensor: Sensor = Sensor::w1(DEVICE);
+    let row = sample(pi, &sh_reg, &mut conn, &sensor).unwrap();
+
+    // 2, 1 with its dot, 5, 0
+    let bytes = [0b1010_0100, 0b0111_1001, 0b1001_0010, 0b1100_0000];
+    let events = daemon.backend().take_events();
+    assert_eq!(events, frame(&bytes));
+    assert_eq!(latched::<4>(&events), bytes);
+
+    assert!(row.to_csv().ends_with(",21.5"), "{}", row.to_csv());
+    let stored = temperatures::table
+        .select(temperatures::temperature)
+        .load::<f32>(&mut conn)
+        .unwrap();
+    assert_eq!(stored, [21.5]);
+}
+
+#[test]
+fn missing_sensor_stores_nothing() {
+    let daemon = FakeDaemon::new().unwrap();
+    let pi =
+        ReconnectingPi::new(&daemon.addr().ip().to_string(), &daemon.port().to_string()).unwrap();
+    let pi: &dyn GpioBackend = &pi;
+
+    let sh_reg = sh_reg(pi);
+    daemon.backend().take_events();
+
+    let mut conn = database();
+    let sensor: Sensor = Sensor::w1(DEVICE);
+    let err = sample(pi, &sh_reg, &mut conn, &sensor).unwrap_err();
+    assert!(err.is::<Misread>());
+
+    assert_eq!(daemon.backend().take_events(), []);
+    let stored = temperatures::table.count().get_result::<i64>(&mut conn);
+    assert_eq!(stored, Ok(0));
+}
+
+#[test]
+fn storage_errors_are_not_misreads() {
+    let path = format!("/sys/bus/w1/devices/{DEVICE}/temperature");
+    let daemon = FakeDaemon::start(Mock::new().with_file(path, "21500\n")).unwrap();
+    let pi =
+        ReconnectingPi::new(&daemon.addr().ip().to_string(), &daemon.port().to_string()).unwrap();
+    let pi: &dyn GpioBackend = &pi;
+
+    let sh_reg = sh_reg(pi);
+
+    // no table to store into
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    let sensor: Sensor = Sensor::w1(DEVICE);
+    let err = sample(pi, &sh_reg, &mut conn, &sensor).unwrap_err();
+    assert!(!err.is::<Misread>());
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::{frame, gpio, latched, sh_reg, DS, SH_CP, ST_CP};
+use cps::mock::{Event, Mock};
+use cps::prelude::*;
+
+const OE: u32 = 23;
+
+#[test]
+fn writes_a_number() {
+    let mock = Mock::new();
+    let sh_reg = sh_reg(&mock);
+    mock.take_events();
+
+    sh_reg.write("21.5").unwrap();
+
+    // blank, 2, 1 with its dot, 5
+    let bytes = [0b1111_1111, 0b1010_0100, 0b0111_1001, 0b1001_0010];
+    let events = mock.take_events();
+    assert_eq!(events, frame(&bytes));
+    assert_eq!(latched::<4>(&events), bytes);
+}
+
+#[test]
+fn batched_frames_latch_the_same() {
+    let mock = Mock::new();
+    let sh_reg = sh_reg(&mock).batched();
+    mock.take_events();
+
+    sh_reg.write("-1.0").unwrap();
+
+    let bytes = [0b1111_1111, 0b1011_1111, 0b0111_1001, 0b1100_0000];
+    let events = mock.take_events();
+    assert_eq!(events, frame(&bytes));
+    assert_eq!(latched::<4>(&events), bytes);
+}
+
+#[test]
+fn pins_are_configured_and_released() {
+    let mock = Mock::new();
+    let sh_reg = sh_reg(&mock);
+
+    for pin in [DS, SH_CP, ST_CP] {
+        assert_eq!(mock.mode(gpio(pin)), Some(GpioMode::Output));
+    }
+
+    drop(sh_reg);
+
+    for pin in [DS, SH_CP, ST_CP] {
+        assert_eq!(mock.mode(gpio(pin)), Some(GpioMode::Input));
+    }
+}
+
+#[test]
+fn any_output_pins_will_do() {
+    let mock = Mock::new();
+    let pin = |gpio| Output::new(&mock, gpio, GpioLevel::Low).unwrap();
+    let sh_reg =
+        ShiftRegister::<4, _, _, _>::new(pin(gpio(DS)), pin(gpio(SH_CP)), pin(gpio(ST_CP)))
+            .oe(pin(gpio(OE)));
+    mock.take_events();
+
+    sh_reg.output_enable().unwrap();
+    sh_reg.write("8888").unwrap();
+
+    let bytes = [0b1000_0000; 4];
+    let events = mock.take_events();
+    assert_eq!(events[0], Event::Write(gpio(OE), GpioLevel::Low));
+    assert_eq!(events[1..], frame(&bytes));
+    assert_eq!(latched::<4>(&events[1..]), bytes);
+}